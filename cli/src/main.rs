@@ -0,0 +1,346 @@
+//! `farmer-house-admin`, the command line tool of the PDA Authority.
+//!
+//! Every command that changes the farm sends a single transaction signed by the authority keypair, built
+//! with `farmer-house-client`. Run it against a local validator with `--url http://127.0.0.1:8899`.
+
+use {
+    anchor_lang::{AccountDeserialize, Owner},
+    anchor_spl::token::{spl_token, Mint},
+    clap::{Parser, Subcommand},
+    farmer_house::{CropStage, CropState, FarmsPda, FieldState, Species, StageMetadata},
+    farmer_house_client::{pda::*, Farm, ShopPrices},
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+        transaction::Transaction,
+    },
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+    std::{error::Error, fmt::Debug},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[clap(
+    name = "farmer-house-admin",
+    about = "Runs a FarmerHouse farm as its PDA Authority"
+)]
+struct Cli {
+    /// RPC URL of the cluster
+    #[clap(long, short = 'u', default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair of the PDA Authority
+    #[clap(long, short = 'k', default_value = "~/.config/solana/id.json")]
+    keypair: String,
+    /// Instance id of the farm
+    #[clap(long, default_value_t = 0)]
+    instance: u16,
+    /// Runs the farm of a deployment from before farms had instance ids, derived without one
+    #[clap(long, conflicts_with = "instance")]
+    legacy: bool,
+    /// Mint of the CurrencyToken, needed by `init` and `withdraw`
+    #[clap(long)]
+    currency_mint: Option<Pubkey>,
+    /// Name of the escrow constraint model
+    #[clap(long, default_value = "Basic Farm")]
+    constraint_model_name: String,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initializes the Farms PDA and its CurrencyToken account
+    Init {
+        #[clap(long)]
+        seed_collection: Pubkey,
+        #[clap(long)]
+        sapling_collection: Pubkey,
+        #[clap(long)]
+        ripe_collection: Pubkey,
+        #[clap(long)]
+        field_collection: Pubkey,
+    },
+    /// Upgrades the Farms PDA to the current layout, from v1 with `--legacy`
+    Migrate,
+    /// Creates the escrow constraint model fields are bound to
+    ConstraintModel {
+        #[clap(long, default_value = "")]
+        schema_uri: String,
+    },
+    /// Moves field, seed or fertilizer tokens from the authority into the shop's inventory
+    Stock {
+        #[clap(long)]
+        mint: Pubkey,
+        /// Amount in base units, 1 for an NFT
+        #[clap(long, default_value_t = 1)]
+        amount: u64,
+    },
+    /// Sets the prices of the shop, in base units of the CurrencyToken
+    SetPrices {
+        #[clap(long)]
+        field: u64,
+        #[clap(long)]
+        seed: u64,
+        #[clap(long)]
+        fertilizer: u64,
+        #[clap(long)]
+        seed_buyback: u64,
+        #[clap(long)]
+        sapling_buyback: u64,
+        #[clap(long)]
+        ripe_buyback: u64,
+        #[clap(long)]
+        field_buyback: u64,
+        /// Share of market sales taken as a fee, in basis points
+        #[clap(long)]
+        market_fee_bps: u16,
+    },
+    /// Withdraws CurrencyToken from the shop's treasury
+    Withdraw {
+        #[clap(long)]
+        amount: u64,
+        /// Owner of the receiving account, the authority by default
+        #[clap(long)]
+        destination: Option<Pubkey>,
+    },
+    /// Prints a decoded FarmerHouse account
+    Inspect {
+        #[clap(subcommand)]
+        account: InspectAccount,
+    },
+}
+
+#[derive(Subcommand)]
+enum InspectAccount {
+    /// The Farms PDA and the balance of its treasury
+    Farms,
+    Species {
+        species_id: u8,
+    },
+    StageMetadata {
+        species_id: u8,
+        stage: String,
+    },
+    Field {
+        field_mint: Pubkey,
+    },
+    Crop {
+        crop_mint: Pubkey,
+    },
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    let keypair_path = match cli.keypair.strip_prefix("~/") {
+        Some(path) => format!("{}/{path}", std::env::var("HOME")?),
+        None => cli.keypair.clone(),
+    };
+    let authority = read_keypair_file(&keypair_path)
+        .map_err(|err| format!("can't read keypair {keypair_path}: {err}"))?;
+
+    let spl_mint = cli.currency_mint.unwrap_or_default();
+    let farm = if cli.legacy {
+        Farm::legacy(authority.pubkey(), spl_mint, &cli.constraint_model_name)
+    } else {
+        Farm::new(
+            cli.instance,
+            authority.pubkey(),
+            spl_mint,
+            &cli.constraint_model_name,
+        )
+    };
+    let require_currency_mint = || {
+        cli.currency_mint
+            .map(|_| ())
+            .ok_or("--currency-mint is required by this command")
+    };
+
+    match cli.command {
+        Command::Init {
+            seed_collection,
+            sapling_collection,
+            ripe_collection,
+            field_collection,
+        } => {
+            require_currency_mint()?;
+            send(
+                &rpc,
+                &authority,
+                &[farm.initialize_farms_pda(
+                    seed_collection,
+                    sapling_collection,
+                    ripe_collection,
+                    field_collection,
+                )],
+            )?;
+            println!("Farms PDA: {}", farm.farms_pda());
+        }
+        Command::Migrate => {
+            send(&rpc, &authority, &[farm.migrate_farms_pda()])?;
+            println!("Migrated Farms PDA: {}", farm.farms_pda());
+        }
+        Command::ConstraintModel { schema_uri } => {
+            send(
+                &rpc,
+                &authority,
+                &[farm.initialize_constraint_model(&schema_uri)],
+            )?;
+            println!(
+                "Escrow constraint model: {}",
+                farm.escrow_constraint_model()
+            );
+        }
+        Command::Stock { mint, amount } => {
+            let decimals = Mint::try_deserialize(&mut &rpc.get_account_data(&mint)?[..])?.decimals;
+            let farms_pda = farm.farms_pda();
+            send(
+                &rpc,
+                &authority,
+                &[
+                    create_associated_token_account_idempotent(
+                        &authority.pubkey(),
+                        &farms_pda,
+                        &mint,
+                        &spl_token::ID,
+                    ),
+                    spl_token::instruction::transfer_checked(
+                        &spl_token::ID,
+                        &get_associated_token_address(&authority.pubkey(), &mint),
+                        &mint,
+                        &get_associated_token_address(&farms_pda, &mint),
+                        &authority.pubkey(),
+                        &[],
+                        amount,
+                        decimals,
+                    )?,
+                ],
+            )?;
+        }
+        Command::SetPrices {
+            field,
+            seed,
+            fertilizer,
+            seed_buyback,
+            sapling_buyback,
+            ripe_buyback,
+            field_buyback,
+            market_fee_bps,
+        } => {
+            send(
+                &rpc,
+                &authority,
+                &[farm.update_prices(ShopPrices {
+                    field,
+                    seed,
+                    fertilizer,
+                    seed_buyback,
+                    sapling_buyback,
+                    ripe_buyback,
+                    field_buyback,
+                    market_fee_bps,
+                })],
+            )?;
+        }
+        Command::Withdraw {
+            amount,
+            destination,
+        } => {
+            require_currency_mint()?;
+            let destination = destination.unwrap_or(authority.pubkey());
+            send(
+                &rpc,
+                &authority,
+                &[
+                    create_associated_token_account_idempotent(
+                        &authority.pubkey(),
+                        &destination,
+                        &spl_mint,
+                        &spl_token::ID,
+                    ),
+                    farm.withdraw_treasury(destination, amount),
+                ],
+            )?;
+        }
+        Command::Inspect { account } => {
+            let farms_pda = farm.farms_pda();
+            match account {
+                InspectAccount::Farms => {
+                    print_account::<FarmsPda>(&rpc, &farms_pda)?;
+                    if cli.currency_mint.is_some() {
+                        let treasury = get_associated_token_address(&farms_pda, &spl_mint);
+                        println!(
+                            "Treasury: {}",
+                            rpc.get_token_account_balance(&treasury)?.ui_amount_string
+                        );
+                    }
+                }
+                InspectAccount::Species { species_id } => {
+                    print_account::<Species>(&rpc, &find_species_pda(&farms_pda, species_id).0)?
+                }
+                InspectAccount::StageMetadata { species_id, stage } => {
+                    let stage = match stage.as_str() {
+                        "sapling" => CropStage::Sapling,
+                        "ripe" => CropStage::Ripe,
+                        _ => return Err("stage must be sapling or ripe".into()),
+                    };
+                    print_account::<StageMetadata>(
+                        &rpc,
+                        &find_stage_metadata_pda(&farms_pda, species_id, stage).0,
+                    )?
+                }
+                InspectAccount::Field { field_mint } => print_account::<FieldState>(
+                    &rpc,
+                    &find_field_state_pda(&farms_pda, &field_mint).0,
+                )?,
+                InspectAccount::Crop { crop_mint } => print_account::<CropState>(
+                    &rpc,
+                    &find_crop_state_pda(&farms_pda, &crop_mint).0,
+                )?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn send(rpc: &RpcClient, authority: &Keypair, instructions: &[Instruction]) -> Result<()> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&authority.pubkey()),
+        &[authority],
+        rpc.get_latest_blockhash()?,
+    );
+    println!(
+        "Signature: {}",
+        rpc.send_and_confirm_transaction(&transaction)?
+    );
+    Ok(())
+}
+
+fn print_account<T: AccountDeserialize + Owner + Debug>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<()> {
+    let account = rpc.get_account(address)?;
+    if account.owner != T::owner() {
+        return Err(format!("{address} is not owned by the FarmerHouse program").into());
+    }
+    println!(
+        "{address}\n{:#?}",
+        T::try_deserialize(&mut &account.data[..])?
+    );
+    Ok(())
+}
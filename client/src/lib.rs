@@ -0,0 +1,1445 @@
+//! Instruction builders for the FarmerHouse program.
+//!
+//! A [`Farm`] holds what identifies a farm instance, and derives every other account an
+//! instruction needs, so callers only pass the mints and wallets involved.
+
+pub mod pda;
+
+use {
+    anchor_lang::{
+        prelude::{AccountMeta, Pubkey},
+        solana_program::{instruction::Instruction, system_program, sysvar},
+        InstructionData, ToAccountMetas,
+    },
+    anchor_spl::{associated_token::get_associated_token_address, token},
+    farmer_house::{accounts, instruction, ID as FARMER_HOUSE_ID, TOMATO_SPECIES_ID},
+    mpl_token_metadata::ID as TOKEN_METADATA_ID,
+    mpl_trifle::ID as TRIFLE_PROGRAM_ID,
+    pda::*,
+};
+
+pub use farmer_house::{
+    view, CompanionEffect, CoopMember, CropStage, EventConfig, Farmhand, GrowthConfig, LeaseTerms,
+    MutationConfig, ProduceConfig, RecipeConfig, RecipeInput, ShopPrices, StageCreator,
+    StageMetadataData,
+};
+
+/// A farm instance, identified by its instance id and run by its PDA Authority
+#[derive(Clone, Debug)]
+pub struct Farm {
+    pub instance_id: u16,
+    /// Whether the farm was migrated from v1 and is derived without its instance id
+    pub legacy_seeds: bool,
+    pub authority: Pubkey,
+    pub spl_mint: Pubkey,
+    pub constraint_model_name: String,
+}
+
+impl Farm {
+    pub fn new(instance_id: u16, authority: Pubkey, spl_mint: Pubkey, constraint_model_name: &str) -> Self {
+        Self {
+            instance_id,
+            legacy_seeds: false,
+            authority,
+            spl_mint,
+            constraint_model_name: constraint_model_name.to_string(),
+        }
+    }
+
+    /// The farm of a deployment from before farms had instance ids, to migrate and run it
+    pub fn legacy(authority: Pubkey, spl_mint: Pubkey, constraint_model_name: &str) -> Self {
+        Self {
+            legacy_seeds: true,
+            ..Self::new(0, authority, spl_mint, constraint_model_name)
+        }
+    }
+
+    pub fn farms_pda(&self) -> Pubkey {
+        if self.legacy_seeds {
+            find_legacy_farms_pda().0
+        } else {
+            find_farms_pda(self.instance_id).0
+        }
+    }
+
+    pub fn escrow_constraint_model(&self) -> Pubkey {
+        find_escrow_constraint_model(&self.authority, &self.constraint_model_name).0
+    }
+
+    pub fn initialize_farms_pda(
+        &self,
+        tomato_seed_collection_address: Pubkey,
+        tomato_sappling_collection_address: Pubkey,
+        ripe_tomato_collection_address: Pubkey,
+        field_collection_address: Pubkey,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::InitFarmsPda {
+                pda_authority: self.authority,
+                farms_pda,
+                pda_associated_token_account: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                system_program: system_program::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                farmer_house_program_data: find_program_data().0,
+            },
+            instruction::InitializeFarmsPda {
+                instance_id: self.instance_id,
+                tomato_seed_collection_address,
+                tomato_sappling_collection_address,
+                ripe_tomato_collection_address,
+                field_collection_address,
+            },
+        )
+    }
+
+    pub fn initialize_constraint_model(&self, schema_uri: &str) -> Instruction {
+        build(
+            accounts::InitConstraintModel {
+                escrow_constraint_model: self.escrow_constraint_model(),
+                farms_pda: self.farms_pda(),
+                pda_authority: self.authority,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::InitializeConstraintModel {
+                name: self.constraint_model_name.clone(),
+                schema_uri: schema_uri.to_string(),
+            },
+        )
+    }
+
+    pub fn migrate_farms_pda(&self) -> Instruction {
+        build(
+            accounts::MigrateFarmsPda {
+                farms_pda: self.farms_pda(),
+                pda_authority: self.authority,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::MigrateFarmsPda {},
+        )
+    }
+
+    pub fn update_growth_config(&self, growth_config: GrowthConfig) -> Instruction {
+        build(
+            accounts::UpdateGrowthConfig {
+                farms_pda: self.farms_pda(),
+                pda_authority: self.authority,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::UpdateGrowthConfig { growth_config },
+        )
+    }
+
+    pub fn update_prices(&self, prices: ShopPrices) -> Instruction {
+        build(
+            accounts::UpdatePrices {
+                farms_pda: self.farms_pda(),
+                pda_authority: self.authority,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::UpdatePrices { prices },
+        )
+    }
+
+    /// Withdraws `amount` of the currency token from the farm's treasury into the ATA of `destination`
+    pub fn withdraw_treasury(&self, destination: Pubkey, amount: u64) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::WithdrawTreasury {
+                farms_pda,
+                pda_authority: self.authority,
+                spl_ata_source: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&destination, &self.spl_mint),
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::WithdrawTreasury { amount },
+        )
+    }
+
+    pub fn initialize_fertilizer(&self, fertilizer_mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::InitFertilizer {
+                farms_pda,
+                pda_authority: self.authority,
+                pda_fertilizer_token_account: get_associated_token_address(&farms_pda, &fertilizer_mint),
+                fertilizer_mint,
+                system_program: system_program::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::InitializeFertilizer {},
+        )
+    }
+
+    pub fn add_tool_slot(&self, tool_collection_mint: Pubkey) -> Instruction {
+        build(
+            accounts::AddToolSlot {
+                escrow_constraint_model: self.escrow_constraint_model(),
+                farms_pda: self.farms_pda(),
+                pda_authority: self.authority,
+                tool_collection_metadata: find_metadata(&tool_collection_mint).0,
+                tool_collection_mint,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::AddToolSlot {},
+        )
+    }
+
+    pub fn set_stage_metadata(
+        &self,
+        species_id: u8,
+        stage: CropStage,
+        data: StageMetadataData,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SetStageMetadata {
+                farms_pda,
+                pda_authority: self.authority,
+                stage_metadata: find_stage_metadata_pda(&farms_pda, species_id, stage).0,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetStageMetadata {
+                species_id,
+                stage,
+                data,
+            },
+        )
+    }
+
+    pub fn register_species(
+        &self,
+        species_id: u8,
+        seed_collection_address: Pubkey,
+        sapling_collection_address: Pubkey,
+        ripe_collection_address: Pubkey,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::RegisterSpecies {
+                farms_pda,
+                pda_authority: self.authority,
+                species: find_species_pda(&farms_pda, species_id).0,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::RegisterSpecies {
+                species_id,
+                seed_collection_address,
+                sapling_collection_address,
+                ripe_collection_address,
+            },
+        )
+    }
+
+    /// Sets up the produce `species_id` yields when harvested, creating its mint on first use
+    pub fn set_produce(&self, species_id: u8, config: ProduceConfig) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let produce_mint = find_produce_mint(&farms_pda, species_id).0;
+        build(
+            accounts::SetProduce {
+                farms_pda,
+                pda_authority: self.authority,
+                species: (species_id != TOMATO_SPECIES_ID).then(|| find_species_pda(&farms_pda, species_id).0),
+                produce: find_produce_pda(&farms_pda, species_id).0,
+                produce_mint,
+                pda_produce_token_account: get_associated_token_address(&farms_pda, &produce_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetProduce { species_id, config },
+        )
+    }
+
+    /// Sets the companion planting effects of `species_id`, replacing the previous ones
+    pub fn set_companions(&self, species_id: u8, effects: Vec<CompanionEffect>) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SetCompanions {
+                farms_pda,
+                pda_authority: self.authority,
+                species: (species_id != TOMATO_SPECIES_ID).then(|| find_species_pda(&farms_pda, species_id).0),
+                companions: find_companions_pda(&farms_pda, species_id).0,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetCompanions { species_id, effects },
+        )
+    }
+
+    /// Sets up the mutation of `species_id`, keeping the supply already mutated
+    pub fn set_mutation(&self, species_id: u8, config: MutationConfig) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SetMutation {
+                farms_pda,
+                pda_authority: self.authority,
+                species: (species_id != TOMATO_SPECIES_ID).then(|| find_species_pda(&farms_pda, species_id).0),
+                mutation: find_mutation_pda(&farms_pda, species_id).0,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetMutation { species_id, config },
+        )
+    }
+
+    /// Sets the chances of the events revealed when harvesting crops planted from now on
+    pub fn set_events(&self, config: EventConfig) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SetEvents {
+                farms_pda,
+                pda_authority: self.authority,
+                events: find_events_pda(&farms_pda).0,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetEvents { config },
+        )
+    }
+
+    /// Sets up recipe `recipe_id`, creating its output mint on first use
+    pub fn set_recipe(&self, recipe_id: u8, config: RecipeConfig) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SetRecipe {
+                farms_pda,
+                pda_authority: self.authority,
+                recipe: find_recipe_pda(&farms_pda, recipe_id).0,
+                output_mint: find_recipe_mint(&farms_pda, recipe_id).0,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SetRecipe { recipe_id, config },
+        )
+    }
+
+    pub fn buy_field(&self, user: Pubkey, field_mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        build(
+            accounts::BuyField {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&user, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                field_ata_source: get_associated_token_address(&farms_pda, &field_mint),
+                field_ata_destination: get_associated_token_address(&user, &field_mint),
+                field_mint,
+                field_metadata: find_metadata(&field_mint).0,
+                field_master_edition: find_master_edition(&field_mint).0,
+                escrow_account: find_escrow(&field_mint, &trifle).0,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                trifle_account: trifle,
+                rent: sysvar::rent::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                token_metadata_program: TOKEN_METADATA_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::BuyField {},
+        )
+    }
+
+    /// Sells an empty field back to the shop for its field buyback
+    pub fn sell_field(&self, user: Pubkey, field_mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SellField {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&user, &self.spl_mint),
+                field_mint,
+                field_ata_source: get_associated_token_address(&user, &field_mint),
+                field_ata_destination: get_associated_token_address(&farms_pda, &field_mint),
+                field_metadata: find_metadata(&field_mint).0,
+                trifle_account: find_trifle(&field_mint, &farms_pda).0,
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SellField {},
+        )
+    }
+
+    /// `species_id` is left out for tomato seeds
+    pub fn buy_seed(&self, user: Pubkey, seed_mint: Pubkey, species_id: Option<u8>) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::BuySeed {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&user, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                seed_ata_source: get_associated_token_address(&farms_pda, &seed_mint),
+                seed_ata_destination: get_associated_token_address(&user, &seed_mint),
+                seed_mint,
+                seed_metadata: find_metadata(&seed_mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::BuySeed {},
+        )
+    }
+
+    pub fn buy_fertilizer(&self, user: Pubkey, fertilizer_mint: Pubkey, amount: u64) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::BuyFertilizer {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&user, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                fertilizer_ata_source: get_associated_token_address(&farms_pda, &fertilizer_mint),
+                fertilizer_ata_destination: get_associated_token_address(&user, &fertilizer_mint),
+                fertilizer_mint,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::BuyFertilizer { amount },
+        )
+    }
+
+    /// `graded` passes the crop's `CropState`, which is closed once the crop is sold, and `mutated` the
+    /// `Mutation` of its species for a mutated crop
+    pub fn sell_crop(
+        &self,
+        user: Pubkey,
+        crop_mint: Pubkey,
+        graded: bool,
+        species_id: Option<u8>,
+        mutated: bool,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::SellCrop {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&user, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                crop_ata_source: get_associated_token_address(&user, &crop_mint),
+                crop_ata_destination: get_associated_token_address(&farms_pda, &crop_mint),
+                crop_metadata: find_metadata(&crop_mint).0,
+                crop_state: graded.then(|| find_crop_state_pda(&farms_pda, &crop_mint).0),
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                produce: None,
+                mutation: mutated.then(|| {
+                    find_mutation_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0
+                }),
+                coop: None,
+                coop_vault: None,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SellCrop { amount: 1 },
+        )
+    }
+
+    /// Sells a graded crop the co-op of the field harvested out of its vault, paying each of `members`,
+    /// in the order of the co-op, their share of the price
+    pub fn sell_coop_crop(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        members: &[Pubkey],
+        species_id: Option<u8>,
+        mutated: bool,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let coop = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop).0;
+        let mut instruction = build(
+            accounts::SellCrop {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&user, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                crop_ata_source: get_associated_token_address(&coop_vault, &crop_mint),
+                crop_ata_destination: get_associated_token_address(&farms_pda, &crop_mint),
+                crop_metadata: find_metadata(&crop_mint).0,
+                crop_state: Some(find_crop_state_pda(&farms_pda, &crop_mint).0),
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                produce: None,
+                mutation: mutated.then(|| {
+                    find_mutation_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0
+                }),
+                coop: Some(coop),
+                coop_vault: Some(coop_vault),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SellCrop { amount: 1 },
+        );
+        instruction.accounts.extend(members.iter().map(|member| {
+            AccountMeta::new(get_associated_token_address(member, &self.spl_mint), false)
+        }));
+        instruction
+    }
+
+    /// Sells `amount` of the produce `species_id` yields back to the shop
+    pub fn sell_produce(&self, user: Pubkey, species_id: u8, amount: u64) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let produce_mint = find_produce_mint(&farms_pda, species_id).0;
+        build(
+            accounts::SellCrop {
+                farms_pda,
+                user_account: user,
+                spl_ata_source: get_associated_token_address(&farms_pda, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&user, &self.spl_mint),
+                spl_mint: self.spl_mint,
+                crop_ata_source: get_associated_token_address(&user, &produce_mint),
+                crop_ata_destination: get_associated_token_address(&farms_pda, &produce_mint),
+                crop_metadata: find_metadata(&produce_mint).0,
+                crop_state: None,
+                species: None,
+                produce: Some(find_produce_pda(&farms_pda, species_id).0),
+                mutation: None,
+                coop: None,
+                coop_vault: None,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::SellCrop { amount },
+        )
+    }
+
+    /// Plants a seed, or replants a sapling, in `crop_slot` of the field. The `Events` address is always
+    /// passed, a new crop committing to the slot revealing its harvest event once they are set. A field
+    /// held by a vault is planted through it.
+    pub fn plant_seed(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        crop_slot: &str,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            species_id,
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let leased = lessor.is_some();
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        let escrow = find_escrow(&field_mint, &trifle).0;
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand_pda).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop_pda).0;
+        let holder = field_holder(
+            user,
+            leased.then_some(lease_vault),
+            farmhand.map(|_| farmhand_vault),
+            coop.then_some(coop_vault),
+        );
+        build(
+            accounts::PlantSeed {
+                trifle_account: trifle,
+                farms_pda,
+                user_account: user,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                escrow_account: escrow,
+                field_mint,
+                field_token_account: get_associated_token_address(&holder, &field_mint),
+                field_master_edition: find_master_edition(&field_mint).0,
+                attribute_mint: crop_mint,
+                attribute_src_token_account: get_associated_token_address(&user, &crop_mint),
+                attribute_dst_token_account: get_associated_token_address(&escrow, &crop_mint),
+                attribute_metadata: find_metadata(&crop_mint).0,
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                events: find_events_pda(&farms_pda).0,
+                lease: leased.then_some(lease),
+                lease_vault: leased.then_some(lease_vault),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                farmhand_vault: farmhand.map(|_| farmhand_vault),
+                coop: coop.then_some(coop_pda),
+                coop_vault: coop.then_some(coop_vault),
+                field_state: find_field_state_pda(&farms_pda, &field_mint).0,
+                token_metadata_program: TOKEN_METADATA_ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                rent: sysvar::rent::ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+            },
+            instruction::PlantSeed {
+                crop_slot: crop_slot.to_string(),
+            },
+        )
+    }
+
+    /// `next_stage` is the stage the crop grows to, whose `StageMetadata` address is always passed
+    /// along with the `Companions` of the crop's species. A ripening crop rolls for the `Mutation` of
+    /// its species when `options` passes it.
+    pub fn water(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        crop_slot: &str,
+        next_stage: CropStage,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            species_id,
+            reveal,
+            mutation,
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let leased = lessor.is_some();
+        let farms_pda = self.farms_pda();
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let holder = field_holder(
+            user,
+            leased.then(|| find_lease_vault_pda(&lease).0),
+            farmhand.map(|_| find_farmhand_vault_pda(&farmhand_pda).0),
+            coop.then(|| find_coop_vault_pda(&coop_pda).0),
+        );
+        build(
+            accounts::Water {
+                farms_pda,
+                user_account: user,
+                field_mint,
+                field_associated_token: get_associated_token_address(&holder, &field_mint),
+                crop_mint,
+                crop_metadata: find_metadata(&crop_mint).0,
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                field_state: find_field_state_pda(&farms_pda, &field_mint).0,
+                stage_metadata: find_stage_metadata_pda(
+                    &farms_pda,
+                    species_id.unwrap_or(TOMATO_SPECIES_ID),
+                    next_stage,
+                )
+                .0,
+                companions: find_companions_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0,
+                mutation: mutation.then(|| {
+                    find_mutation_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0
+                }),
+                slot_hashes: reveal.then_some(sysvar::slot_hashes::ID),
+                lease: leased.then_some(lease),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                coop: coop.then_some(coop_pda),
+                trifle_account: find_trifle(&field_mint, &farms_pda).0,
+                token_metadata_program: TOKEN_METADATA_ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                system_program: system_program::ID,
+            },
+            instruction::Water {
+                crop_slot: crop_slot.to_string(),
+            },
+        )
+    }
+
+    pub fn fertilize(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        fertilizer_mint: Pubkey,
+        crop_slot: &str,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let farms_pda = self.farms_pda();
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let holder = field_holder(
+            user,
+            lessor.map(|_| find_lease_vault_pda(&lease).0),
+            farmhand.map(|_| find_farmhand_vault_pda(&farmhand_pda).0),
+            coop.then(|| find_coop_vault_pda(&coop_pda).0),
+        );
+        build(
+            accounts::Fertilize {
+                farms_pda,
+                user_account: user,
+                field_mint,
+                field_associated_token: get_associated_token_address(&holder, &field_mint),
+                trifle_account: find_trifle(&field_mint, &farms_pda).0,
+                crop_mint,
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                field_state: find_field_state_pda(&farms_pda, &field_mint).0,
+                fertilizer_mint,
+                fertilizer_token_account: get_associated_token_address(&user, &fertilizer_mint),
+                lease: lessor.map(|_| lease),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                coop: coop.then_some(coop_pda),
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::Fertilize {
+                crop_slot: crop_slot.to_string(),
+            },
+        )
+    }
+
+    /// Harvests the ripe crop in `crop_slot` of the field. The `Produce` and `Companions` addresses of
+    /// the crop's species are always passed, and `options` needs `produce` whenever the species has
+    /// `Produce` set. The crop of a field held by a co-op goes to the vault of the co-op.
+    pub fn harvest_crop(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        crop_slot: &str,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            species_id,
+            produce,
+            reveal,
+            mutation,
+            lessor,
+            farmhand,
+            coop,
+        } = options;
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        let escrow = find_escrow(&field_mint, &trifle).0;
+        let produce_mint = find_produce_mint(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0;
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand_pda).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop_pda).0;
+        let holder = field_holder(
+            user,
+            lessor.map(|_| lease_vault),
+            farmhand.map(|_| farmhand_vault),
+            coop.then_some(coop_vault),
+        );
+        build(
+            accounts::HarvestCrop {
+                trifle_account: trifle,
+                farms_pda,
+                user_account: user,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                escrow_account: escrow,
+                field_mint,
+                field_token_account: get_associated_token_address(&holder, &field_mint),
+                field_metadata: find_metadata(&field_mint).0,
+                field_edition: find_master_edition(&field_mint).0,
+                attribute_mint: crop_mint,
+                attribute_src_token_account: get_associated_token_address(&escrow, &crop_mint),
+                attribute_dst_token_account: get_associated_token_address(
+                    &match farmhand {
+                        _ if coop => coop_vault,
+                        Some(owner) if !produce => owner,
+                        _ => user,
+                    },
+                    &crop_mint,
+                ),
+                attribute_metadata: find_metadata(&crop_mint).0,
+                attribute_edition: produce.then(|| find_master_edition(&crop_mint).0),
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                field_state: find_field_state_pda(&farms_pda, &field_mint).0,
+                produce: find_produce_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0,
+                produce_mint: produce.then_some(produce_mint),
+                produce_token_account: produce
+                    .then(|| get_associated_token_address(&farmhand.unwrap_or(user), &produce_mint)),
+                companions: find_companions_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0,
+                events: find_events_pda(&farms_pda).0,
+                slot_hashes: reveal.then_some(sysvar::slot_hashes::ID),
+                mutation: mutation.then(|| {
+                    find_mutation_pda(&farms_pda, species_id.unwrap_or(TOMATO_SPECIES_ID)).0
+                }),
+                lease: lessor.map(|_| lease),
+                lease_vault: lessor.map(|_| lease_vault),
+                spl_ata_source: lessor.map(|_| get_associated_token_address(&user, &self.spl_mint)),
+                lessor_spl_ata: lessor
+                    .map(|lessor| get_associated_token_address(&lessor, &self.spl_mint)),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                farmhand_vault: farmhand.map(|_| farmhand_vault),
+                farmhand_owner: farmhand,
+                coop: coop.then_some(coop_pda),
+                coop_vault: coop.then_some(coop_vault),
+                token_metadata_program: TOKEN_METADATA_ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                rent: sysvar::rent::ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::HarvestCrop {
+                crop_slot: crop_slot.to_string(),
+            },
+        )
+    }
+
+    /// Records the slot hash a crop planted with events committed to, once the slot has passed
+    pub fn reveal_crop(&self, crop_mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::RevealCrop {
+                farms_pda,
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                slot_hashes: sysvar::slot_hashes::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::RevealCrop {},
+        )
+    }
+
+    pub fn clear_slot(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        crop_mint: Pubkey,
+        crop_slot: &str,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            species_id,
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        let escrow = find_escrow(&field_mint, &trifle).0;
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand_pda).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop_pda).0;
+        let holder = field_holder(
+            user,
+            lessor.map(|_| lease_vault),
+            farmhand.map(|_| farmhand_vault),
+            coop.then_some(coop_vault),
+        );
+        build(
+            accounts::ClearSlot {
+                trifle_account: trifle,
+                farms_pda,
+                user_account: user,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                escrow_account: escrow,
+                field_mint,
+                field_token_account: get_associated_token_address(&holder, &field_mint),
+                field_metadata: find_metadata(&field_mint).0,
+                field_edition: find_master_edition(&field_mint).0,
+                attribute_mint: crop_mint,
+                attribute_src_token_account: get_associated_token_address(&escrow, &crop_mint),
+                attribute_dst_token_account: get_associated_token_address(&user, &crop_mint),
+                attribute_metadata: find_metadata(&crop_mint).0,
+                crop_state: find_crop_state_pda(&farms_pda, &crop_mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                field_state: find_field_state_pda(&farms_pda, &field_mint).0,
+                lease: lessor.map(|_| lease),
+                lease_vault: lessor.map(|_| lease_vault),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                farmhand_vault: farmhand.map(|_| farmhand_vault),
+                coop: coop.then_some(coop_pda),
+                coop_vault: coop.then_some(coop_vault),
+                token_metadata_program: TOKEN_METADATA_ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                rent: sysvar::rent::ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::ClearSlot {
+                crop_slot: crop_slot.to_string(),
+            },
+        )
+    }
+
+    /// Crafts `batches` of a recipe from the user's `inputs`, given in the order of the recipe's inputs.
+    /// `processed` recipes need a `CraftState` to wait out their processing time in, the others hand
+    /// out the `output_nfts` stocked in the shop right away when the recipe has an output collection.
+    pub fn craft(
+        &self,
+        user: Pubkey,
+        recipe_id: u8,
+        inputs: &[CraftInput],
+        output_nfts: &[Pubkey],
+        processed: bool,
+        batches: u64,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let recipe = find_recipe_pda(&farms_pda, recipe_id).0;
+        let output_mint = find_recipe_mint(&farms_pda, recipe_id).0;
+        let mut instruction = build(
+            accounts::Craft {
+                farms_pda,
+                user_account: user,
+                recipe,
+                output_mint,
+                output_token_account: get_associated_token_address(&user, &output_mint),
+                craft_state: processed.then(|| find_craft_state_pda(&recipe, &user).0),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                token_metadata_program: TOKEN_METADATA_ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::Craft { batches },
+        );
+        for input in inputs {
+            match input {
+                CraftInput::Mint(mint) => {
+                    instruction
+                        .accounts
+                        .push(AccountMeta::new(get_associated_token_address(&user, mint), false));
+                    instruction.accounts.push(AccountMeta::new(*mint, false));
+                }
+                CraftInput::Nfts(mints) => {
+                    for mint in mints {
+                        instruction.accounts.extend([
+                            AccountMeta::new(get_associated_token_address(&user, mint), false),
+                            AccountMeta::new(*mint, false),
+                            AccountMeta::new(find_metadata(mint).0, false),
+                            AccountMeta::new(find_master_edition(mint).0, false),
+                        ]);
+                    }
+                }
+            }
+        }
+        self.push_output_nfts(&mut instruction, user, output_nfts);
+        instruction
+    }
+
+    /// Hands out the output of a processed craft once its processing time has passed, the
+    /// `output_nfts` stocked in the shop when the recipe has an output collection
+    pub fn collect_craft(&self, user: Pubkey, recipe_id: u8, output_nfts: &[Pubkey]) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let recipe = find_recipe_pda(&farms_pda, recipe_id).0;
+        let output_mint = find_recipe_mint(&farms_pda, recipe_id).0;
+        let mut instruction = build(
+            accounts::CollectCraft {
+                farms_pda,
+                user_account: user,
+                recipe,
+                output_mint,
+                output_token_account: get_associated_token_address(&user, &output_mint),
+                craft_state: find_craft_state_pda(&recipe, &user).0,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::CollectCraft {},
+        );
+        self.push_output_nfts(&mut instruction, user, output_nfts);
+        instruction
+    }
+
+    fn push_output_nfts(&self, instruction: &mut Instruction, user: Pubkey, output_nfts: &[Pubkey]) {
+        let farms_pda = self.farms_pda();
+        for mint in output_nfts {
+            instruction.accounts.extend([
+                AccountMeta::new(get_associated_token_address(&farms_pda, mint), false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(find_metadata(mint).0, false),
+                AccountMeta::new(get_associated_token_address(&user, mint), false),
+            ]);
+        }
+    }
+
+    /// Moves the user's field into the vault of a new lease, offered on `terms`
+    pub fn offer_lease(&self, user: Pubkey, field_mint: Pubkey, terms: LeaseTerms) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        build(
+            accounts::OfferLease {
+                farms_pda,
+                user_account: user,
+                field_mint,
+                field_metadata: find_metadata(&field_mint).0,
+                field_ata_source: get_associated_token_address(&user, &field_mint),
+                lease,
+                lease_vault,
+                field_ata_destination: get_associated_token_address(&lease_vault, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::OfferLease { terms },
+        )
+    }
+
+    /// Takes the lease `owner` offered on their field, paying them its fee
+    pub fn take_lease(&self, user: Pubkey, field_mint: Pubkey, owner: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        build(
+            accounts::TakeLease {
+                farms_pda,
+                user_account: user,
+                lease: find_lease_pda(&farms_pda, &field_mint).0,
+                spl_ata_source: get_associated_token_address(&user, &self.spl_mint),
+                spl_ata_destination: get_associated_token_address(&owner, &self.spl_mint),
+                token_program: token::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::TakeLease {},
+        )
+    }
+
+    /// Returns the field to the user who offered the lease, `tenant` being whoever took it, if anyone
+    pub fn end_lease(&self, user: Pubkey, field_mint: Pubkey, tenant: Option<Pubkey>) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        build(
+            accounts::EndLease {
+                farms_pda,
+                user_account: user,
+                lease,
+                lease_vault,
+                tenant_account: tenant,
+                field_mint,
+                field_ata_source: get_associated_token_address(&lease_vault, &field_mint),
+                field_ata_destination: get_associated_token_address(&user, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::EndLease {},
+        )
+    }
+
+    /// Moves the user's field into the vault of a new farmhand, letting `delegate` take `actions` on it
+    /// until `expires_at`
+    pub fn delegate_farmhand(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        delegate: Pubkey,
+        actions: u8,
+        expires_at: i64,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let farmhand = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand).0;
+        build(
+            accounts::DelegateFarmhand {
+                farms_pda,
+                user_account: user,
+                field_mint,
+                field_metadata: find_metadata(&field_mint).0,
+                field_ata_source: get_associated_token_address(&user, &field_mint),
+                farmhand,
+                farmhand_vault,
+                field_ata_destination: get_associated_token_address(&farmhand_vault, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::DelegateFarmhand {
+                delegate,
+                actions,
+                expires_at,
+            },
+        )
+    }
+
+    /// Returns the field held by its farmhand to the user who delegated it
+    pub fn revoke_farmhand(&self, user: Pubkey, field_mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let farmhand = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand).0;
+        build(
+            accounts::RevokeFarmhand {
+                farms_pda,
+                user_account: user,
+                farmhand,
+                farmhand_vault,
+                field_mint,
+                field_ata_source: get_associated_token_address(&farmhand_vault, &field_mint),
+                field_ata_destination: get_associated_token_address(&user, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::RevokeFarmhand {},
+        )
+    }
+
+    /// Moves the user's field into the vault of a new co-op of `members`, the user among them
+    pub fn found_coop(&self, user: Pubkey, field_mint: Pubkey, members: Vec<CoopMember>) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let coop = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop).0;
+        build(
+            accounts::FoundCoop {
+                farms_pda,
+                user_account: user,
+                field_mint,
+                field_metadata: find_metadata(&field_mint).0,
+                field_ata_source: get_associated_token_address(&user, &field_mint),
+                coop,
+                coop_vault,
+                field_ata_destination: get_associated_token_address(&coop_vault, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::FoundCoop { members },
+        )
+    }
+
+    /// Dissolves the co-op of the field, handing the field to the user. `cosigners` are the other
+    /// members signing along so the signers hold more than half of the shares
+    pub fn dissolve_coop(&self, user: Pubkey, field_mint: Pubkey, cosigners: &[Pubkey]) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let coop = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop).0;
+        let mut instruction = build(
+            accounts::DissolveCoop {
+                farms_pda,
+                user_account: user,
+                coop,
+                coop_vault,
+                field_mint,
+                field_ata_source: get_associated_token_address(&coop_vault, &field_mint),
+                field_ata_destination: get_associated_token_address(&user, &field_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::DissolveCoop {},
+        );
+        instruction.accounts.extend(
+            cosigners
+                .iter()
+                .map(|cosigner| AccountMeta::new_readonly(*cosigner, true)),
+        );
+        instruction
+    }
+
+    /// Lists the user's field or crop for `price`. A crop of another species than the tomato passes
+    /// `species_id`
+    pub fn create_listing(
+        &self,
+        user: Pubkey,
+        mint: Pubkey,
+        price: u64,
+        species_id: Option<u8>,
+    ) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let listing = find_listing_pda(&farms_pda, &mint).0;
+        build(
+            accounts::CreateListing {
+                farms_pda,
+                user_account: user,
+                nft_mint: mint,
+                nft_metadata: find_metadata(&mint).0,
+                nft_master_edition: find_master_edition(&mint).0,
+                trifle_account: find_trifle(&mint, &farms_pda).0,
+                crop_state: find_crop_state_pda(&farms_pda, &mint).0,
+                species: species_id.map(|species_id| find_species_pda(&farms_pda, species_id).0),
+                nft_ata_source: get_associated_token_address(&user, &mint),
+                listing,
+                nft_ata_destination: get_associated_token_address(&listing, &mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::CreateListing { price },
+        )
+    }
+
+    /// Buys the NFT `seller` listed for `price`
+    pub fn buy_listing(&self, user: Pubkey, mint: Pubkey, seller: Pubkey, price: u64) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let listing = find_listing_pda(&farms_pda, &mint).0;
+        build(
+            accounts::BuyListing {
+                farms_pda,
+                user_account: user,
+                seller,
+                listing,
+                nft_mint: mint,
+                nft_ata_source: get_associated_token_address(&listing, &mint),
+                nft_ata_destination: get_associated_token_address(&user, &mint),
+                spl_ata_source: get_associated_token_address(&user, &self.spl_mint),
+                seller_spl_ata: get_associated_token_address(&seller, &self.spl_mint),
+                treasury_spl_ata: get_associated_token_address(&farms_pda, &self.spl_mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::BuyListing { price },
+        )
+    }
+
+    /// Takes the user's listed NFT off the market
+    pub fn cancel_listing(&self, user: Pubkey, mint: Pubkey) -> Instruction {
+        let farms_pda = self.farms_pda();
+        let listing = find_listing_pda(&farms_pda, &mint).0;
+        build(
+            accounts::CancelListing {
+                farms_pda,
+                user_account: user,
+                listing,
+                nft_mint: mint,
+                nft_ata_source: get_associated_token_address(&listing, &mint),
+                nft_ata_destination: get_associated_token_address(&user, &mint),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+            },
+            instruction::CancelListing {},
+        )
+    }
+
+    pub fn equip_tool(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        tool_mint: Pubkey,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        let escrow = find_escrow(&field_mint, &trifle).0;
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand_pda).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop_pda).0;
+        let holder = field_holder(
+            user,
+            lessor.map(|_| lease_vault),
+            farmhand.map(|_| farmhand_vault),
+            coop.then_some(coop_vault),
+        );
+        build(
+            accounts::EquipTool {
+                trifle_account: trifle,
+                farms_pda,
+                user_account: user,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                escrow_account: escrow,
+                field_mint,
+                field_token_account: get_associated_token_address(&holder, &field_mint),
+                field_master_edition: find_master_edition(&field_mint).0,
+                attribute_mint: tool_mint,
+                attribute_src_token_account: get_associated_token_address(&user, &tool_mint),
+                attribute_dst_token_account: get_associated_token_address(&escrow, &tool_mint),
+                attribute_metadata: find_metadata(&tool_mint).0,
+                lease: lessor.map(|_| lease),
+                lease_vault: lessor.map(|_| lease_vault),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                farmhand_vault: farmhand.map(|_| farmhand_vault),
+                coop: coop.then_some(coop_pda),
+                coop_vault: coop.then_some(coop_vault),
+                token_metadata_program: TOKEN_METADATA_ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                rent: sysvar::rent::ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+            },
+            instruction::EquipTool {},
+        )
+    }
+
+    pub fn unequip_tool(
+        &self,
+        user: Pubkey,
+        field_mint: Pubkey,
+        tool_mint: Pubkey,
+        options: CropOptions,
+    ) -> Instruction {
+        let CropOptions {
+            lessor,
+            farmhand,
+            coop,
+            ..
+        } = options;
+        let farms_pda = self.farms_pda();
+        let trifle = find_trifle(&field_mint, &farms_pda).0;
+        let escrow = find_escrow(&field_mint, &trifle).0;
+        let lease = find_lease_pda(&farms_pda, &field_mint).0;
+        let lease_vault = find_lease_vault_pda(&lease).0;
+        let farmhand_pda = find_farmhand_pda(&farms_pda, &field_mint).0;
+        let farmhand_vault = find_farmhand_vault_pda(&farmhand_pda).0;
+        let coop_pda = find_coop_pda(&farms_pda, &field_mint).0;
+        let coop_vault = find_coop_vault_pda(&coop_pda).0;
+        let holder = field_holder(
+            user,
+            lessor.map(|_| lease_vault),
+            farmhand.map(|_| farmhand_vault),
+            coop.then_some(coop_vault),
+        );
+        build(
+            accounts::UnequipTool {
+                trifle_account: trifle,
+                farms_pda,
+                user_account: user,
+                escrow_constraint_model: self.escrow_constraint_model(),
+                escrow_account: escrow,
+                field_mint,
+                field_token_account: get_associated_token_address(&holder, &field_mint),
+                field_metadata: find_metadata(&field_mint).0,
+                field_edition: find_master_edition(&field_mint).0,
+                attribute_mint: tool_mint,
+                attribute_src_token_account: get_associated_token_address(&escrow, &tool_mint),
+                attribute_dst_token_account: get_associated_token_address(&user, &tool_mint),
+                attribute_metadata: find_metadata(&tool_mint).0,
+                lease: lessor.map(|_| lease),
+                lease_vault: lessor.map(|_| lease_vault),
+                farmhand: farmhand.map(|_| farmhand_pda),
+                farmhand_vault: farmhand.map(|_| farmhand_vault),
+                coop: coop.then_some(coop_pda),
+                coop_vault: coop.then_some(coop_vault),
+                token_metadata_program: TOKEN_METADATA_ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: token::ID,
+                system_program: system_program::ID,
+                farmer_house_program: FARMER_HOUSE_ID,
+                rent: sysvar::rent::ID,
+                trifle_program: TRIFLE_PROGRAM_ID,
+                instructions: sysvar::instructions::ID,
+            },
+            instruction::UnequipTool {},
+        )
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    let accounts: Vec<AccountMeta> = accounts.to_account_metas(None);
+    Instruction {
+        program_id: FARMER_HOUSE_ID,
+        accounts,
+        data: data.data(),
+    }
+}
+
+/// The optional accounts of `plant_seed`, `water`, `fertilize`, `harvest_crop`, `clear_slot` and the
+/// tool instructions. The default is a tomato in a field the user holds, passing none of them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CropOptions {
+    /// The registered species of the crop, none for the tomato
+    pub species_id: Option<u8>,
+    /// Passes the accounts to burn a ripe crop for the produce of its species when harvesting
+    pub produce: bool,
+    /// Passes the SlotHashes sysvar, to reveal the slot hash a crop planted with events committed to
+    /// unless it was recorded already
+    pub reveal: bool,
+    /// Passes the `Mutation` of the species, for a ripening crop to roll or a mutated crop to harvest
+    pub mutation: bool,
+    /// Works a field the user leased from `lessor` through the vault of its lease, paying them their
+    /// share of the harvest
+    pub lessor: Option<Pubkey>,
+    /// Works the field `farmhand` delegated to the user through the farmhand vault, its harvest going
+    /// to them
+    pub farmhand: Option<Pubkey>,
+    /// Works a field held by a co-op the user is a member of through the co-op vault
+    pub coop: bool,
+}
+
+/// What the user crafts an input of a recipe from
+#[derive(Clone, Debug)]
+pub enum CraftInput {
+    /// The user's tokens of a fungible input
+    Mint(Pubkey),
+    /// The NFTs burned for an input matched by collection, its `amount` times the batches
+    Nfts(Vec<Pubkey>),
+}
+
+/// The owner of the field token account: the vault holding the field when there is one, else the user
+fn field_holder(
+    user: Pubkey,
+    lease_vault: Option<Pubkey>,
+    farmhand_vault: Option<Pubkey>,
+    coop_vault: Option<Pubkey>,
+) -> Pubkey {
+    lease_vault.or(farmhand_vault).or(coop_vault).unwrap_or(user)
+}
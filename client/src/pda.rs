@@ -0,0 +1,183 @@
+//! Addresses of the accounts FarmerHouse, Token Metadata and Trifle derive from their seeds
+
+use {
+    anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable},
+    farmer_house::{CropStage, ID as FARMER_HOUSE_ID},
+    mpl_token_metadata::{
+        escrow::find_escrow_account,
+        pda::{find_master_edition_account, find_metadata_account},
+        state::EscrowAuthority,
+    },
+    mpl_trifle::pda::{find_escrow_constraint_model_address, find_trifle_address},
+};
+
+pub fn find_farms_pda(instance_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"farmer-house-farms",
+            FARMER_HOUSE_ID.as_ref(),
+            &instance_id.to_le_bytes(),
+        ],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+/// The farm of a deployment from before farms had instance ids, kept at its address once migrated
+pub fn find_legacy_farms_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-farms", FARMER_HOUSE_ID.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FARMER_HOUSE_ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+pub fn find_crop_state_pda(farms_pda: &Pubkey, crop_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-crop", farms_pda.as_ref(), crop_mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_field_state_pda(farms_pda: &Pubkey, field_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-field", farms_pda.as_ref(), field_mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_species_pda(farms_pda: &Pubkey, species_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-species", farms_pda.as_ref(), &[species_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_stage_metadata_pda(farms_pda: &Pubkey, species_id: u8, stage: CropStage) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"farmer-house-stage",
+            farms_pda.as_ref(),
+            &[species_id],
+            &[stage as u8],
+        ],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_produce_pda(farms_pda: &Pubkey, species_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-produce", farms_pda.as_ref(), &[species_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_produce_mint(farms_pda: &Pubkey, species_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-produce-mint", farms_pda.as_ref(), &[species_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_companions_pda(farms_pda: &Pubkey, species_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-companions", farms_pda.as_ref(), &[species_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_mutation_pda(farms_pda: &Pubkey, species_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-mutation", farms_pda.as_ref(), &[species_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_events_pda(farms_pda: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farmer-house-events", farms_pda.as_ref()], &FARMER_HOUSE_ID)
+}
+
+pub fn find_recipe_pda(farms_pda: &Pubkey, recipe_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-recipe", farms_pda.as_ref(), &[recipe_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_recipe_mint(farms_pda: &Pubkey, recipe_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-recipe-mint", farms_pda.as_ref(), &[recipe_id]],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_craft_state_pda(recipe: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-craft", recipe.as_ref(), user.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_lease_pda(farms_pda: &Pubkey, field_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-lease", farms_pda.as_ref(), field_mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_lease_vault_pda(lease: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farmer-house-lease-vault", lease.as_ref()], &FARMER_HOUSE_ID)
+}
+
+pub fn find_farmhand_pda(farms_pda: &Pubkey, field_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-farmhand", farms_pda.as_ref(), field_mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_farmhand_vault_pda(farmhand: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farmer-house-farmhand-vault", farmhand.as_ref()], &FARMER_HOUSE_ID)
+}
+
+pub fn find_coop_pda(farms_pda: &Pubkey, field_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-coop", farms_pda.as_ref(), field_mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_coop_vault_pda(coop: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farmer-house-coop-vault", coop.as_ref()], &FARMER_HOUSE_ID)
+}
+
+pub fn find_listing_pda(farms_pda: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer-house-listing", farms_pda.as_ref(), mint.as_ref()],
+        &FARMER_HOUSE_ID,
+    )
+}
+
+pub fn find_escrow_constraint_model(creator: &Pubkey, name: &str) -> (Pubkey, u8) {
+    find_escrow_constraint_model_address(creator, name)
+}
+
+/// The Trifle of a field, whose authority is the farms PDA that sold it
+pub fn find_trifle(field_mint: &Pubkey, farms_pda: &Pubkey) -> (Pubkey, u8) {
+    find_trifle_address(field_mint, farms_pda)
+}
+
+/// The escrow holding the crops planted in a field, created by its Trifle
+pub fn find_escrow(field_mint: &Pubkey, trifle: &Pubkey) -> (Pubkey, u8) {
+    find_escrow_account(field_mint, &EscrowAuthority::Creator(*trifle))
+}
+
+pub fn find_metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    find_metadata_account(mint)
+}
+
+pub fn find_master_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    find_master_edition_account(mint)
+}
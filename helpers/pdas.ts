@@ -45,4 +45,80 @@ export const findEscrowPda = (mint: PublicKey, authority: number, creator?: Publ
         seeds,
         new PublicKey(TM_PROGRAM_ADDRESS)
     );
-}
\ No newline at end of file
+}
+export const findCropStatePda = (farmsPda: PublicKey, cropMint: PublicKey, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-crop'),
+            farmsPda.toBuffer(),
+            cropMint.toBuffer(),
+        ],
+        programId
+    );
+}
+
+export const findSpeciesPda = (farmsPda: PublicKey, speciesId: number, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-species'),
+            farmsPda.toBuffer(),
+            Uint8Array.from([speciesId]),
+        ],
+        programId
+    );
+}
+
+export const findStageMetadataPda = (farmsPda: PublicKey, speciesId: number, stage: number, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-stage'),
+            farmsPda.toBuffer(),
+            Uint8Array.from([speciesId]),
+            Uint8Array.from([stage]),
+        ],
+        programId
+    );
+}
+
+export const findFieldStatePda = (farmsPda: PublicKey, fieldMint: PublicKey, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-field'),
+            farmsPda.toBuffer(),
+            fieldMint.toBuffer(),
+        ],
+        programId
+    );
+}
+
+export const findProducePda = (farmsPda: PublicKey, speciesId: number, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-produce'),
+            farmsPda.toBuffer(),
+            Uint8Array.from([speciesId]),
+        ],
+        programId
+    );
+}
+
+export const findCompanionsPda = (farmsPda: PublicKey, speciesId: number, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-companions'),
+            farmsPda.toBuffer(),
+            Uint8Array.from([speciesId]),
+        ],
+        programId
+    );
+}
+
+export const findEventsPda = (farmsPda: PublicKey, programId: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('farmer-house-events'),
+            farmsPda.toBuffer(),
+        ],
+        programId
+    );
+}
@@ -0,0 +1,218 @@
+use {
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, Coop, CoopMember, FarmsPda,
+        MAX_COOP_MEMBERS,
+    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{associated_token, token},
+    mpl_token_metadata::{
+        state::{Metadata, TokenMetadataAccount},
+        ID as TOKEN_METADATA_ID,
+    },
+};
+
+/// Founds a co-op of `members` on a field, moving the field into the vault of the co-op
+pub fn found_coop(ctx: Context<FoundCoop>, members: Vec<CoopMember>) -> Result<()> {
+    let user = ctx.accounts.user_account.key();
+    require!(
+        !members.is_empty()
+            && members.len() <= MAX_COOP_MEMBERS
+            && members.iter().any(|member| member.wallet == user)
+            && members.iter().enumerate().all(|(i, member)| {
+                member.shares > 0 && members[..i].iter().all(|other| other.wallet != member.wallet)
+            }),
+        FarmerHouseError::InvalidCoop
+    );
+
+    let metadata_info = Metadata::from_account_info(&ctx.accounts.field_metadata.to_account_info())?;
+    require!(
+        matches!(
+            &metadata_info.collection,
+            Some(collection) if collection.key == ctx.accounts.farms_pda.field_collection_address
+        ),
+        FarmerHouseError::CollectionMismatch
+    );
+    require_keys_eq!(
+        metadata_info.mint,
+        ctx.accounts.field_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+
+    let coop_info = &mut ctx.accounts.coop;
+    coop_info.bump = *ctx.bumps.get("coop").unwrap();
+    coop_info.vault_bump = *ctx.bumps.get("coop_vault").unwrap();
+    coop_info.field_mint = ctx.accounts.field_mint.key();
+    coop_info.crops = 0;
+    coop_info.members = members;
+
+    // Transfer fieldNFT from ATA of user to ATA of the co-op vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+/// Hands the field to the member dissolving the co-op, along with what is left in the vault. Members
+/// holding more than half of the shares sign along, the others passed as `remaining_accounts`. Crops
+/// still in the field stay with it, the harvests of the co-op have to be sold before dissolving it.
+pub fn dissolve_coop<'info>(ctx: Context<'_, '_, '_, 'info, DissolveCoop<'info>>) -> Result<()> {
+    let coop_info = &ctx.accounts.coop;
+    let user = ctx.accounts.user_account.key();
+    require!(
+        coop_info.member(&user).is_some(),
+        FarmerHouseError::NotCoopMember
+    );
+    require_eq!(coop_info.crops, 0, FarmerHouseError::CoopCropsUnsold);
+
+    let signed_shares: u64 = coop_info
+        .members
+        .iter()
+        .filter(|member| {
+            member.wallet == user
+                || ctx
+                    .remaining_accounts
+                    .iter()
+                    .any(|account| account.is_signer && account.key() == member.wallet)
+        })
+        .map(|member| member.shares as u64)
+        .sum();
+    require!(
+        signed_shares * 2 > coop_info.total_shares(),
+        FarmerHouseError::CoopQuorum
+    );
+
+    let coop = coop_info.key();
+    let vault_seeds: &[&[u8]] = &[b"farmer-house-coop-vault", coop.as_ref(), &[coop_info.vault_bump]];
+
+    // Transfer fieldNFT from ATA of the co-op vault to ATA of the user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.coop_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.field_ata_source.to_account_info(),
+            destination: ctx.accounts.user_account.to_account_info(),
+            authority: ctx.accounts.coop_vault.to_account_info(),
+        },
+        &[vault_seeds],
+    ))?;
+
+    let float = ctx.accounts.coop_vault.lamports();
+    if float > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.coop_vault.to_account_info(),
+                    to: ctx.accounts.user_account.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            float,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FoundCoop<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: found_coop checks this is the metadata of the field
+    #[account(owner = TOKEN_METADATA_ID)]
+    pub field_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init,
+        payer = user_account,
+        space = Coop::SPACE,
+        seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump
+    )]
+    pub coop: Box<Account<'info, Coop>>,
+    #[account(seeds = [b"farmer-house-coop-vault", coop.key().as_ref()], bump)]
+    pub coop_vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = coop_vault
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct DissolveCoop<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump = coop.bump
+    )]
+    pub coop: Box<Account<'info, Coop>>,
+    #[account(mut, seeds = [b"farmer-house-coop-vault", coop.key().as_ref()], bump = coop.vault_bump)]
+    pub coop_vault: SystemAccount<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = coop_vault
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -0,0 +1,353 @@
+use {
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, CraftState, FarmsPda, Recipe,
+        RecipeInput,
+    },
+    anchor_lang::{prelude::*, solana_program::program::invoke},
+    anchor_spl::{associated_token, token},
+    mpl_token_metadata::{
+        instruction::burn_nft,
+        state::{Metadata, TokenMetadataAccount},
+        ID as TOKEN_METADATA_ID,
+    },
+};
+
+/// Burns the inputs of `batches` of the recipe, passed in `remaining_accounts` in the order of the
+/// recipe's inputs: the user's token account and the mint of a fungible input, or the token account,
+/// mint, metadata and master edition of each NFT burned for an input matched by collection. The output
+/// is handed out right away, or recorded in the `CraftState` until the recipe's processing time has
+/// passed.
+pub fn craft<'info>(ctx: Context<'_, '_, '_, 'info, Craft<'info>>, batches: u64) -> Result<()> {
+    let recipe_info = &ctx.accounts.recipe;
+
+    require!(batches > 0, FarmerHouseError::AmountMismatch);
+    require_keys_eq!(
+        ctx.accounts.output_mint.key(),
+        recipe_info.output_mint,
+        FarmerHouseError::RecipeMismatch
+    );
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    for input in &recipe_info.config.inputs {
+        let amount = input
+            .amount
+            .checked_mul(batches)
+            .ok_or(FarmerHouseError::AmountMismatch)?;
+
+        if input.collection {
+            for _ in 0..amount {
+                let nft = take_accounts(&mut remaining_accounts, 4)?;
+                burn_nft_input(&ctx, input, nft)?;
+            }
+            continue;
+        }
+
+        let accounts = take_accounts(&mut remaining_accounts, 2)?;
+        let (token_account, mint) = (&accounts[0], &accounts[1]);
+        require_keys_eq!(mint.key(), input.mint, FarmerHouseError::RecipeMismatch);
+
+        // The token program rejects a token account of another mint or with too few tokens
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: mint.clone(),
+                    from: token_account.clone(),
+                    authority: ctx.accounts.user_account.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let amount = recipe_info
+        .config
+        .output_amount
+        .checked_mul(batches)
+        .ok_or(FarmerHouseError::AmountMismatch)?;
+
+    let output = Output {
+        farms_pda: &ctx.accounts.farms_pda,
+        user_account: &ctx.accounts.user_account,
+        output_mint: ctx.accounts.output_mint.to_account_info(),
+        output_token_account: ctx.accounts.output_token_account.to_account_info(),
+        associated_token_program: &ctx.accounts.associated_token_program,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+        farmer_house_program: &ctx.accounts.farmer_house_program,
+    };
+    if recipe_info.config.processing_time == 0 {
+        return output.hand_out(recipe_info, remaining_accounts, amount);
+    }
+    require!(
+        remaining_accounts.is_empty(),
+        FarmerHouseError::RecipeMismatch
+    );
+
+    let recipe = recipe_info.key();
+    let ready_at = Clock::get()?
+        .unix_timestamp
+        .saturating_add(recipe_info.config.processing_time);
+    let craft_state_info = ctx
+        .accounts
+        .craft_state
+        .as_mut()
+        .ok_or(FarmerHouseError::RecipeMismatch)?;
+    require!(
+        craft_state_info.amount == 0,
+        FarmerHouseError::CraftInProgress
+    );
+
+    craft_state_info.bump = *ctx.bumps.get("craft_state").unwrap();
+    craft_state_info.recipe = recipe;
+    craft_state_info.amount = amount;
+    craft_state_info.ready_at = ready_at;
+    msg!("Crafting {} until {}", amount, ready_at);
+
+    Ok(())
+}
+
+/// Hands out the output recorded in the `CraftState`, the NFTs of a recipe with an output collection
+/// passed in `remaining_accounts` like to `craft`
+pub fn collect_craft<'info>(ctx: Context<'_, '_, '_, 'info, CollectCraft<'info>>) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.output_mint.key(),
+        ctx.accounts.recipe.output_mint,
+        FarmerHouseError::RecipeMismatch
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.craft_state.ready_at,
+        FarmerHouseError::CraftProcessing
+    );
+
+    Output {
+        farms_pda: &ctx.accounts.farms_pda,
+        user_account: &ctx.accounts.user_account,
+        output_mint: ctx.accounts.output_mint.to_account_info(),
+        output_token_account: ctx.accounts.output_token_account.to_account_info(),
+        associated_token_program: &ctx.accounts.associated_token_program,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+        farmer_house_program: &ctx.accounts.farmer_house_program,
+    }
+    .hand_out(
+        &ctx.accounts.recipe,
+        ctx.remaining_accounts,
+        ctx.accounts.craft_state.amount,
+    )
+}
+
+/// Takes the next `count` accounts off `accounts`, failing when fewer were passed than the recipe needs
+fn take_accounts<'a, 'info>(
+    accounts: &mut &'a [AccountInfo<'info>],
+    count: usize,
+) -> Result<&'a [AccountInfo<'info>]> {
+    require!(accounts.len() >= count, FarmerHouseError::RecipeMismatch);
+    let (taken, rest) = accounts.split_at(count);
+    *accounts = rest;
+    Ok(taken)
+}
+
+/// Burns an NFT of the farm in the collection of `input`, passed as its token account, mint, metadata
+/// and master edition. Crops leave their collection unverified, so only NFTs the farms PDA has the
+/// update authority of count.
+fn burn_nft_input<'info>(
+    ctx: &Context<'_, '_, '_, 'info, Craft<'info>>,
+    input: &RecipeInput,
+    nft: &[AccountInfo<'info>],
+) -> Result<()> {
+    let (token_account, mint, metadata, edition) = (&nft[0], &nft[1], &nft[2], &nft[3]);
+    let metadata_info = Metadata::from_account_info(metadata)?;
+    require!(
+        metadata_info.mint == mint.key()
+            && metadata_info.update_authority == ctx.accounts.farms_pda.key()
+            && matches!(
+                &metadata_info.collection,
+                Some(collection) if collection.key == input.mint
+            ),
+        FarmerHouseError::RecipeMismatch
+    );
+
+    invoke(
+        &burn_nft(
+            TOKEN_METADATA_ID,
+            metadata.key(),
+            ctx.accounts.user_account.key(),
+            mint.key(),
+            token_account.key(),
+            edition.key(),
+            token::ID,
+            None,
+        ),
+        &[
+            metadata.clone(),
+            ctx.accounts.user_account.to_account_info(),
+            mint.clone(),
+            token_account.clone(),
+            edition.clone(),
+            ctx.accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// The accounts `craft` and `collect_craft` hand the output of a recipe to the user with
+struct Output<'a, 'info> {
+    farms_pda: &'a Account<'info, FarmsPda>,
+    user_account: &'a Signer<'info>,
+    output_mint: AccountInfo<'info>,
+    output_token_account: AccountInfo<'info>,
+    associated_token_program: &'a Program<'info, associated_token::AssociatedToken>,
+    token_program: &'a Program<'info, token::Token>,
+    system_program: &'a Program<'info, System>,
+    farmer_house_program: &'a Program<'info, program_farmer_house>,
+}
+
+impl<'a, 'info> Output<'a, 'info> {
+    /// Mints `amount` of the recipe's output mint, or for a recipe with an output collection hands out
+    /// `amount` NFTs of it from the shop, each passed in `nfts` as the shop's token account, the mint,
+    /// the metadata and the user's token account, created if needed
+    fn hand_out(self, recipe: &Recipe, nfts: &[AccountInfo<'info>], amount: u64) -> Result<()> {
+        let farms_pda_seeds: &[&[u8]] = &[
+            b"farmer-house-farms",
+            &self.farmer_house_program.key().to_bytes(),
+            &self.farms_pda.instance_seed(),
+            &[self.farms_pda.bump],
+        ];
+
+        let output_collection = match recipe.config.output_collection {
+            Some(output_collection) => output_collection,
+            None => {
+                require!(nfts.is_empty(), FarmerHouseError::RecipeMismatch);
+                token::mint_to(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::MintTo {
+                            mint: self.output_mint,
+                            to: self.output_token_account,
+                            authority: self.farms_pda.to_account_info(),
+                        },
+                        &[farms_pda_seeds],
+                    ),
+                    amount,
+                )?;
+                msg!("Crafted {}", amount);
+                return Ok(());
+            }
+        };
+
+        require!(
+            u64::try_from(nfts.len()) == Ok(amount.saturating_mul(4)),
+            FarmerHouseError::RecipeMismatch
+        );
+        for nft in nfts.chunks(4) {
+            let (shop_token_account, mint, metadata, user_token_account) =
+                (&nft[0], &nft[1], &nft[2], &nft[3]);
+            let metadata_info = Metadata::from_account_info(metadata)?;
+            require!(
+                metadata_info.mint == mint.key()
+                    && matches!(
+                        &metadata_info.collection,
+                        Some(collection) if collection.key == output_collection
+                    ),
+                FarmerHouseError::RecipeMismatch
+            );
+
+            associated_token::create_idempotent(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: self.user_account.to_account_info(),
+                    associated_token: user_token_account.clone(),
+                    authority: self.user_account.to_account_info(),
+                    mint: mint.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            ))?;
+            // The token program rejects a token account of the shop that doesn't hold the NFT
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: shop_token_account.clone(),
+                        to: user_token_account.clone(),
+                        authority: self.farms_pda.to_account_info(),
+                    },
+                    &[farms_pda_seeds],
+                ),
+                1,
+            )?;
+        }
+        msg!("Crafted {} of collection {}", amount, output_collection);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Craft<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+    #[account(seeds = [b"farmer-house-recipe", farms_pda.key().as_ref(), &[recipe.recipe_id]], bump = recipe.bump)]
+    pub recipe: Box<Account<'info, Recipe>>,
+    #[account(mut)]
+    pub output_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = output_mint,
+        associated_token::authority = user_account
+    )]
+    pub output_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// Only needed by recipes with a processing time
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        space = CraftState::SPACE,
+        seeds = [b"farmer-house-craft", recipe.key().as_ref(), user_account.key().as_ref()],
+        bump
+    )]
+    pub craft_state: Option<Box<Account<'info, CraftState>>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    /// CHECK: Metaplex burns the NFT inputs of a recipe
+    #[account(address = TOKEN_METADATA_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct CollectCraft<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+    #[account(seeds = [b"farmer-house-recipe", farms_pda.key().as_ref(), &[recipe.recipe_id]], bump = recipe.bump)]
+    pub recipe: Box<Account<'info, Recipe>>,
+    #[account(mut)]
+    pub output_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = output_mint,
+        associated_token::authority = user_account
+    )]
+    pub output_token_account: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-craft", recipe.key().as_ref(), user_account.key().as_ref()],
+        bump = craft_state.bump
+    )]
+    pub craft_state: Box<Account<'info, CraftState>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -24,4 +24,80 @@ pub enum FarmerHouseError {
     CropNotRipe,
     #[msg("The crop is ready to be harvested and does not need to be watered anymore")]
     CropReady,
+    #[msg("The growth config values are invalid")]
+    InvalidGrowthConfig,
+    #[msg("The crop went too long without water and withered, clear its slot")]
+    CropWithered,
+    #[msg("The crop has not withered and cannot be cleared from its slot")]
+    CropNotWithered,
+    #[msg("The crop is still growing, wait a bit before watering it again")]
+    CropGrowing,
+    #[msg("The crop is not growing, fertilizer would have no effect")]
+    CropNotGrowing,
+    #[msg("The species is not registered or does not match the crop")]
+    InvalidSpecies,
+    #[msg("The account is not a farms PDA of this program")]
+    InvalidFarmsPda,
+    #[msg("The farms PDA is already on the latest version")]
+    FarmsPdaUpToDate,
+    #[msg("The stage metadata is invalid or does not match the stage the crop grows to")]
+    InvalidStageMetadata,
+    #[msg("The crop slot does not exist")]
+    InvalidCropSlot,
+    #[msg("The shop can't give fields, seeds or fertilizer away for free, buy fields back for more than it sells them, nor take a market fee above 100%")]
+    InvalidPrices,
+    #[msg("Produce has to yield at least one unit, more for better grades, and be worth something")]
+    InvalidProduceConfig,
+    #[msg("The produce accounts are missing or do not match the species of the crop")]
+    ProduceMismatch,
+    #[msg("A recipe takes one to four distinct inputs other than its output, and has to yield something")]
+    InvalidRecipe,
+    #[msg("The accounts do not match the inputs or output of the recipe")]
+    RecipeMismatch,
+    #[msg("A craft of this recipe is already processing, collect it first")]
+    CraftInProgress,
+    #[msg("The craft is still processing, wait a bit before collecting it")]
+    CraftProcessing,
+    #[msg("A species can have up to eight distinct companions, each changing growth time by at most 100%")]
+    InvalidCompanions,
+    #[msg("Events need a reveal delay, and their chances can't add up to more than 100%")]
+    InvalidEventConfig,
+    #[msg("The events or SlotHashes accounts are missing or do not match")]
+    EventsMismatch,
+    #[msg("The slot deciding the event of the harvest has not passed yet, wait a bit before harvesting")]
+    CropNotRevealed,
+    #[msg("A mutation needs a collection, valid metadata, a chance of at most 100% and a supply cap no lower than its supply")]
+    InvalidMutation,
+    #[msg("Harvest or clear every crop and unequip the tool before selling the field")]
+    FieldNotEmpty,
+    #[msg("A lease has to run for some time, and can't give the owner more than all of the proceeds")]
+    InvalidLeaseTerms,
+    #[msg("The lease accounts are missing or do not match the field")]
+    LeaseMismatch,
+    #[msg("The lease has expired or was taken by someone else")]
+    LeaseExpired,
+    #[msg("The lease has already been taken")]
+    LeaseTaken,
+    #[msg("The lease is still running, wait until it expires to end it")]
+    LeaseActive,
+    #[msg("A farmhand needs at least one of the water, plant and harvest actions, and has to expire in the future")]
+    InvalidFarmhand,
+    #[msg("The farmhand accounts are missing or do not match the field")]
+    FarmhandMismatch,
+    #[msg("The farmhand is not allowed to do this, or has expired")]
+    FarmhandNotAllowed,
+    #[msg("A co-op needs one to eight distinct members with shares, its founder among them")]
+    InvalidCoop,
+    #[msg("The co-op accounts are missing or do not match, and a co-op harvests crops into its vault")]
+    CoopMismatch,
+    #[msg("Only members of the co-op can do this")]
+    NotCoopMember,
+    #[msg("Members holding more than half of the shares have to sign to dissolve the co-op")]
+    CoopQuorum,
+    #[msg("A listing needs a price")]
+    InvalidListing,
+    #[msg("The listing is for another seller or price")]
+    ListingMismatch,
+    #[msg("The crops harvested by the co-op have to be sold before dissolving it")]
+    CoopCropsUnsold,
 }
\ No newline at end of file
@@ -1,13 +1,25 @@
 use {
-    crate::{errors::*, program::FarmerHouse as program_farmer_house, FarmsPda},
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, vault::FieldHolder, Companions,
+        Coop, CropEvent, CropGrade, CropStage, CropState, Events, Farmhand, FarmsPda, FieldSlot,
+        FieldState, Lease, Mutation, Produce, SoilSlot, Species, StageCreator, StageMetadata,
+        StageMetadataData, initialized, TOOL_SLOT,
+    },
     anchor_lang::{
         prelude::*,
-        solana_program::program::{invoke, invoke_signed},
+        solana_program::{
+            hash::hashv,
+            program::{invoke, invoke_signed},
+            sysvar::slot_hashes,
+        },
+    },
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address},
+        token,
     },
-    anchor_spl::{associated_token, token},
     mpl_token_metadata::{
-        instruction::update_metadata_accounts_v2,
-        state::{Collection, DataV2, Metadata, TokenMetadataAccount},
+        instruction::{burn_nft, update_metadata_accounts_v2},
+        state::{Collection, Creator, DataV2, Metadata, TokenMetadataAccount},
         utils::assert_derivation,
         ID as TOKEN_METADATA_ID,
     },
@@ -50,28 +62,139 @@ pub fn plant_seed(ctx: Context<PlantSeed>, crop_slot: String) -> Result<()> {
         FarmerHouseError::ProgramMismatch
     );
 
+    // Crops can be planted as seeds, or replanted as saplings after being transferred out mid-growth
+    let species = ctx
+        .accounts
+        .farms_pda
+        .species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
     let metadata_info =
         Metadata::from_account_info(&ctx.accounts.attribute_metadata.to_account_info());
-    require_eq!(
-        ctx.accounts.farms_pda.tomato_seed_collection_address,
-        metadata_info
-            .as_ref()
-            .unwrap()
-            .collection
-            .as_ref()
-            .unwrap()
-            .key,
-        FarmerHouseError::CollectionMismatch
-    );
+    let stage = species
+        .stage_of(
+            &metadata_info
+                .as_ref()
+                .unwrap()
+                .collection
+                .as_ref()
+                .ok_or(FarmerHouseError::CollectionMismatch)?
+                .key,
+        )
+        .ok_or(FarmerHouseError::CollectionMismatch)?;
+    require!(stage != CropStage::Ripe, FarmerHouseError::CropReady);
 
     // We don't verify collection, in a real case scenario it would be important to have it verified and check if it is so
 
-    invoke(
+    let field_state_info = &mut ctx.accounts.field_state;
+    if field_state_info.field_mint == Pubkey::default() {
+        field_state_info.bump = *ctx.bumps.get("field_state").unwrap();
+        field_state_info.field_mint = ctx.accounts.field_mint.key();
+        field_state_info.soil = [SoilSlot::default(); 6];
+    }
+    *field_state_info.slot_mut(&crop_slot)? = FieldSlot {
+        crop_mint: ctx.accounts.attribute_mint.key(),
+        species_id: species.species_id,
+        stage: Some(stage),
+    };
+
+    // Rotating species restores the soil, the same species keeps depleting it
+    let soil = field_state_info.soil_mut(&crop_slot)?;
+    soil.plant(species.species_id);
+    let soil = *soil;
+
+    assert_derivation(
+        &TRIFLE_PROGRAM_ID,
+        &ctx.accounts.trifle_account,
+        &[
+            TRIFLE_SEED.as_bytes(),
+            ctx.accounts.field_mint.key().as_ref(),
+            ctx.accounts.farms_pda.key().as_ref(),
+        ],
+    )?;
+    let trifle = Trifle::from_account_info(&ctx.accounts.trifle_account.to_account_info())?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let growth_config = ctx.accounts.farms_pda.growth_config;
+    let crop_state_info = &mut ctx.accounts.crop_state;
+
+    if crop_state_info.crop_mint == Pubkey::default() {
+        // Start tracking the care history of the crop from the moment it is planted
+        crop_state_info.bump = *ctx.bumps.get("crop_state").unwrap();
+        crop_state_info.crop_mint = ctx.accounts.attribute_mint.key();
+        crop_state_info.species_id = species.species_id;
+        crop_state_info.last_cared_at = now;
+        crop_state_info.ready_at = now + soil.growth_time(growth_config.stage_growth_time);
+        crop_state_info.on_time_waterings = 0;
+        crop_state_info.missed_windows = 0;
+        crop_state_info.grade = None;
+        crop_state_info.tool_equipped = tool_equipped(&trifle);
+        crop_state_info.mutated = false;
+
+        // Commit to the hash of a slot to come, which decides the event of the harvest
+        crop_state_info.reveal_slot = match initialized::<Events>(&ctx.accounts.events)? {
+            Some(events_info) => Clock::get()?
+                .slot
+                .saturating_add(events_info.config.reveal_delay),
+            None => 0,
+        };
+        crop_state_info.revealed_hash = None;
+    } else {
+        // A replanted crop keeps its care history, so it can't dodge withering by changing fields
+        require_eq!(
+            crop_state_info.species_id,
+            species.species_id,
+            FarmerHouseError::InvalidSpecies
+        );
+        require!(
+            !crop_state_info.is_withered(
+                now,
+                growth_config
+                    .with_tool(crop_state_info.tool_equipped)
+                    .wither_deadline
+            ),
+            FarmerHouseError::CropWithered
+        );
+        crop_state_info.tool_equipped &= tool_equipped(&trifle);
+    }
+    crop_state_info.field_mint = ctx.accounts.field_mint.key();
+
+    // A tenant, farmhand or co-op member plants through the vault holding the field, moving the seed as
+    // its holder
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::PLANT,
+    )?;
+    let holder_info = holder.fund(
+        &ctx.accounts.user_account,
+        ctx.accounts
+            .lease_vault
+            .as_ref()
+            .or(ctx.accounts.farmhand_vault.as_ref())
+            .or(ctx.accounts.coop_vault.as_ref()),
+        &ctx.accounts.system_program,
+    )?;
+    if holder.is_vault() {
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Approve {
+                    to: ctx.accounts.attribute_src_token_account.to_account_info(),
+                    delegate: holder_info.clone(),
+                    authority: ctx.accounts.user_account.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+    }
+
+    holder.invoke(
         &trifle_instruction::transfer_in(
             TRIFLE_PROGRAM_ID,
             ctx.accounts.trifle_account.key(),
             ctx.accounts.farms_pda.key(),
-            ctx.accounts.user_account.key(),
+            holder.key,
             ctx.accounts.escrow_constraint_model.key(),
             ctx.accounts.escrow_account.key(),
             Some(ctx.accounts.field_mint.key()),
@@ -89,7 +212,7 @@ pub fn plant_seed(ctx: Context<PlantSeed>, crop_slot: String) -> Result<()> {
         &[
             ctx.accounts.trifle_account.to_account_info(),
             ctx.accounts.farms_pda.to_account_info(),
-            ctx.accounts.user_account.to_account_info(),
+            holder_info,
             ctx.accounts.escrow_constraint_model.to_account_info(),
             ctx.accounts.escrow_account.to_account_info(),
             ctx.accounts.field_mint.to_account_info(),
@@ -114,18 +237,26 @@ pub fn plant_seed(ctx: Context<PlantSeed>, crop_slot: String) -> Result<()> {
 pub fn water(ctx: Context<Water>, crop_slot: String) -> Result<()> {
     let farms_pda_info = &ctx.accounts.farms_pda;
     let metadata_info = Metadata::from_account_info(&ctx.accounts.crop_metadata.to_account_info());
+    let species =
+        farms_pda_info.species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
 
-    assert_ne!(
-        metadata_info
-            .as_ref()
-            .unwrap()
-            .collection
-            .as_ref()
-            .unwrap()
-            .key,
-        farms_pda_info.ripe_tomato_collection_address,
-        "{}",
-        FarmerHouseError::CropReady
+    let stage = species
+        .stage_of(
+            &metadata_info
+                .as_ref()
+                .unwrap()
+                .collection
+                .as_ref()
+                .ok_or(FarmerHouseError::CollectionMismatch)?
+                .key,
+        )
+        .ok_or(FarmerHouseError::CollectionMismatch)?;
+    let next_stage = stage.next().ok_or(FarmerHouseError::CropReady)?;
+
+    require_eq!(
+        ctx.accounts.crop_state.species_id,
+        species.species_id,
+        FarmerHouseError::InvalidSpecies
     );
 
     assert_eq!(
@@ -143,10 +274,19 @@ pub fn water(ctx: Context<Water>, crop_slot: String) -> Result<()> {
         FarmerHouseError::MintMismatch
     );
 
-    // Confirm user is owner of field_associated_account
+    // Confirm user is owner of field_associated_account, or acts through the lease, farmhand or co-op
+    // whose vault holds it
+    let now = Clock::get()?.unix_timestamp;
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::WATER,
+    )?;
     assert_eq!(
         ctx.accounts.field_associated_token.owner,
-        ctx.accounts.user_account.key(),
+        holder.key,
         "{}",
         FarmerHouseError::OwnerMismatch
     );
@@ -181,58 +321,112 @@ pub fn water(ctx: Context<Water>, crop_slot: String) -> Result<()> {
         FarmerHouseError::TrifleMismatch
     );
 
-    let data: DataV2;
+    // Confirm the crop being watered is the one planted in crop_slot
+    require_eq!(
+        trifle.tokens.get(&crop_slot).unwrap()[0].mint,
+        ctx.accounts.crop_mint.key(),
+        FarmerHouseError::TrifleMismatch
+    );
+    require_eq!(
+        metadata_info.as_ref().unwrap().mint,
+        ctx.accounts.crop_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
 
-    if metadata_info
-        .as_ref()
-        .unwrap()
-        .collection
-        .as_ref()
-        .unwrap()
-        .key
-        == farms_pda_info.tomato_seed_collection_address
-    {
-        data = DataV2 {
-            name: String::from("Tomato Sapling"),
-            symbol: String::from("TOMATO"),
-            uri: String::from(
-                "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
-            ),
-            seller_fee_basis_points: 1,
-            creators: None,
-            collection: Some(Collection {
-                verified: (false),
-                key: (farms_pda_info.tomato_sappling_collection_address),
-            }),
-            uses: None,
-        };
-    } else if metadata_info
-        .as_ref()
-        .unwrap()
-        .collection
-        .as_ref()
-        .unwrap()
-        .key
-        == farms_pda_info.tomato_sappling_collection_address
-    {
-        data = DataV2 {
-            name: String::from("Ripe Tomato"),
-            symbol: String::from("TOMATO"),
-            uri: String::from(
-                "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
-            ),
-            seller_fee_basis_points: 1,
-            creators: None,
-            collection: Some(Collection {
-                verified: (false),
-                key: (farms_pda_info.ripe_tomato_collection_address),
-            }),
-            uses: None,
-        };
-    } else {
-        return err!(FarmerHouseError::CollectionMismatch);
+    // The tool counts for the care since the last watering only when it was equipped back then too
+    let growth_config = farms_pda_info
+        .growth_config
+        .with_tool(ctx.accounts.crop_state.tool_equipped);
+
+    require!(
+        !ctx.accounts
+            .crop_state
+            .is_withered(now, growth_config.wither_deadline),
+        FarmerHouseError::CropWithered
+    );
+
+    require!(
+        now >= ctx.accounts.crop_state.ready_at,
+        FarmerHouseError::CropGrowing
+    );
+
+    ctx.accounts
+        .crop_state
+        .record_watering(now, growth_config.watering_window);
+    ctx.accounts.crop_state.tool_equipped &= tool_equipped(&trifle);
+    // Companions planted next to the crop speed its growth up or slow it down
+    let growth_time = match initialized::<Companions>(&ctx.accounts.companions)? {
+        Some(companions_info) => companions_info.growth_time(
+            growth_config.stage_growth_time,
+            &ctx.accounts.field_state.neighbour_species(&crop_slot),
+        ),
+        None => growth_config.stage_growth_time,
+    };
+    let growth_time = ctx.accounts.field_state.soil(&crop_slot)?.growth_time(growth_time);
+    ctx.accounts.crop_state.ready_at = now + growth_time;
+    ctx.accounts.field_state.slot_mut(&crop_slot)?.stage = Some(next_stage);
+
+    let current_data = &metadata_info.as_ref().unwrap().data;
+    let mut data = DataV2 {
+        name: current_data.name.clone(),
+        symbol: current_data.symbol.clone(),
+        uri: current_data.uri.clone(),
+        seller_fee_basis_points: current_data.seller_fee_basis_points,
+        creators: current_data.creators.clone(),
+        collection: Some(Collection {
+            verified: (false),
+            key: (species.collection_of(next_stage)),
+        }),
+        uses: None,
     };
 
+    // The metadata of the next stage applies whenever the PAW has set it
+    assert_derivation(
+        &crate::ID,
+        &ctx.accounts.stage_metadata,
+        &[
+            b"farmer-house-stage",
+            ctx.accounts.farms_pda.key().as_ref(),
+            &[species.species_id],
+            &[next_stage as u8],
+        ],
+    )
+    .map_err(|_| FarmerHouseError::InvalidStageMetadata)?;
+    if let Some(stage_metadata_info) = initialized::<StageMetadata>(&ctx.accounts.stage_metadata)? {
+        apply_stage_data(
+            &mut data,
+            &stage_metadata_info.data,
+            &current_data.creators,
+            &ctx.accounts.farms_pda.key(),
+        );
+    }
+
+    // A ripening crop committed to a slot hash may mutate into the rare variety, while its supply lasts
+    let reveal_slot = ctx.accounts.crop_state.reveal_slot;
+    let ripening = next_stage == CropStage::Ripe && reveal_slot != 0;
+    if let (true, Some(mutation_info)) = (ripening, ctx.accounts.mutation.as_mut()) {
+        let crop_mint = ctx.accounts.crop_mint.key();
+        let roll = reveal(&mut ctx.accounts.crop_state, ctx.accounts.slot_hashes.as_ref())?
+            .map(|hash| roll_of(&hash, &[crop_mint.as_ref(), b"farmer-house-mutation"]));
+        if mutation_info.available()
+            && matches!(roll, Some(roll) if roll < mutation_info.config.chance_bps as u64)
+        {
+            apply_stage_data(
+                &mut data,
+                &mutation_info.config.data,
+                &current_data.creators,
+                &ctx.accounts.farms_pda.key(),
+            );
+            data.collection = Some(Collection {
+                verified: false,
+                key: mutation_info.config.collection,
+            });
+            mutation_info.supply += 1;
+            ctx.accounts.crop_state.mutated = true;
+            msg!("Crop mutated, {} of {}", mutation_info.supply, mutation_info.config.max_supply);
+        }
+    }
+
     invoke_signed(
         &update_metadata_accounts_v2(
             TOKEN_METADATA_ID,
@@ -250,6 +444,7 @@ pub fn water(ctx: Context<Water>, crop_slot: String) -> Result<()> {
         &[&[
             b"farmer-house-farms",
             ctx.accounts.farmer_house_program.key().as_ref(),
+            &ctx.accounts.farms_pda.instance_seed(),
             &[ctx.accounts.farms_pda.bump],
         ]],
     )?;
@@ -289,21 +484,158 @@ pub fn harvest_crop(ctx: Context<HarvestCrop>, crop_slot: String) -> Result<()>
         FarmerHouseError::ProgramMismatch
     );
 
+    let species = ctx
+        .accounts
+        .farms_pda
+        .species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
     let metadata_info =
         Metadata::from_account_info(&ctx.accounts.attribute_metadata.to_account_info());
-    require_eq!(
-        ctx.accounts.farms_pda.ripe_tomato_collection_address,
-        metadata_info
-            .as_ref()
-            .unwrap()
-            .collection
-            .as_ref()
-            .unwrap()
-            .key,
+    let collection = metadata_info
+        .as_ref()
+        .unwrap()
+        .collection
+        .as_ref()
+        .unwrap()
+        .key;
+    // Mutated crops left the ripe collection for the one of their mutation
+    let mutated = ctx.accounts.crop_state.mutated
+        && matches!(
+            &ctx.accounts.mutation,
+            Some(mutation_info) if mutation_info.config.collection == collection
+        );
+    require!(
+        collection == species.ripe || mutated,
         FarmerHouseError::CollectionMismatch
     );
+    require_eq!(
+        ctx.accounts.crop_state.species_id,
+        species.species_id,
+        FarmerHouseError::InvalidSpecies
+    );
+
+    // Grade the crop based on how well it was cared for while growing, a tool equipped throughout adds
+    // extra yield
+    let crop_state_info = &mut ctx.accounts.crop_state;
+    let mut grade = CropGrade::from_care(crop_state_info.missed_windows);
+    if crop_state_info.tool_equipped {
+        grade = grade.upgraded();
+    }
+
+    // The hash of the slot the crop committed to when planted is only known now, so the outcome can't be
+    // picked in advance
+    if crop_state_info.reveal_slot != 0 {
+        let events_info = initialized::<Events>(&ctx.accounts.events)?
+            .ok_or(FarmerHouseError::EventsMismatch)?;
+        // Only the crop is hashed, the roll stays the same whichever field it is replanted in
+        let roll = reveal(crop_state_info, ctx.accounts.slot_hashes.as_ref())?
+            .map(|hash| roll_of(&hash, &[ctx.accounts.attribute_mint.key().as_ref()]));
+
+        // Pests get into a crop revealed too late to find its hash
+        let event = match roll {
+            Some(roll) => events_info.config.event_of(roll),
+            None => Some(CropEvent::Pests),
+        };
+        if let Some(event) = event {
+            grade = event.apply(grade);
+            msg!("Harvest event {:?}", event);
+        }
+    }
+    crop_state_info.grade = Some(grade);
+    msg!("Crop harvested with grade {:?}", grade);
+
+    let neighbours = ctx.accounts.field_state.neighbour_species(&crop_slot);
+    let soil = *ctx.accounts.field_state.soil(&crop_slot)?;
+    ctx.accounts.field_state.soil_mut(&crop_slot)?.harvest();
+    *ctx.accounts.field_state.slot_mut(&crop_slot)? = FieldSlot::default();
+
+    // What the harvest is worth at the shop, the owner of a leased field gets a share of it
+    let price = match &ctx.accounts.mutation {
+        Some(mutation_info) if mutated => mutation_info.config.sell_price,
+        _ => ctx.accounts.farms_pda.prices.ripe_buyback,
+    };
+    let mut proceeds = grade.price_of(price)?;
+
+    // A tenant or farmhand harvests through the vault holding the field, the crop still goes straight
+    // to the tenant, or to the owner the farmhand works for
+    let lease = ctx.accounts.lease.as_deref();
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        lease,
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::HARVEST,
+    )?;
+    let holder_info = holder.fund(
+        &ctx.accounts.user_account,
+        ctx.accounts
+            .lease_vault
+            .as_ref()
+            .or(ctx.accounts.farmhand_vault.as_ref())
+            .or(ctx.accounts.coop_vault.as_ref()),
+        &ctx.accounts.system_program,
+    )?;
+
+    // The harvests of a co-op stay in its vault until sold, so they can't be turned into produce
+    let produce = initialized::<Produce>(&ctx.accounts.produce)?;
+    if let Some(coop_info) = ctx.accounts.coop.as_deref_mut() {
+        require_keys_eq!(
+            ctx.accounts.attribute_dst_token_account.key(),
+            get_associated_token_address(&holder.key, &ctx.accounts.attribute_mint.key()),
+            FarmerHouseError::CoopMismatch
+        );
+        require!(produce.is_none(), FarmerHouseError::CoopMismatch);
+        coop_info.crops += 1;
+    }
+    let crop_owner = match ctx.accounts.farmhand.as_deref() {
+        Some(farmhand_info) => {
+            let farmhand_owner = ctx
+                .accounts
+                .farmhand_owner
+                .as_ref()
+                .ok_or(FarmerHouseError::FarmhandMismatch)?;
+            require_keys_eq!(
+                farmhand_owner.key(),
+                farmhand_info.owner,
+                FarmerHouseError::FarmhandMismatch
+            );
+            farmhand_owner.to_account_info()
+        }
+        None => ctx.accounts.user_account.to_account_info(),
+    };
+    // The harvester burns a crop turned into produce from their own wallet, any other crop of a farmhand
+    // goes to the owner's wallet
+    let farmhand_crop = ctx.accounts.farmhand.is_some() && produce.is_none();
+    if farmhand_crop {
+        require_keys_eq!(
+            ctx.accounts.attribute_dst_token_account.key(),
+            get_associated_token_address(crop_owner.key, &ctx.accounts.attribute_mint.key()),
+            FarmerHouseError::FarmhandMismatch
+        );
+    }
+    // Token Metadata only creates a missing destination as the ATA of the payer of the transfer, which
+    // is the vault when one holds the field, so the ATA of whoever gets the crop is created up front
+    if holder.is_vault() {
+        let crop_recipient = if ctx.accounts.coop.is_some() {
+            holder_info.clone()
+        } else if farmhand_crop {
+            crop_owner.clone()
+        } else {
+            ctx.accounts.user_account.to_account_info()
+        };
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.user_account.to_account_info(),
+                associated_token: ctx.accounts.attribute_dst_token_account.to_account_info(),
+                authority: crop_recipient,
+                mint: ctx.accounts.attribute_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
 
-    invoke(
+    holder.invoke(
         &trifle_instruction::transfer_out(
             TRIFLE_PROGRAM_ID,
             ctx.accounts.trifle_account.key(),
@@ -313,7 +645,7 @@ pub fn harvest_crop(ctx: Context<HarvestCrop>, crop_slot: String) -> Result<()>
             ctx.accounts.field_mint.key(),
             ctx.accounts.field_metadata.key(),
             Some(ctx.accounts.field_edition.key()),
-            ctx.accounts.user_account.key(),
+            holder.key,
             ctx.accounts.farms_pda.key(),
             ctx.accounts.attribute_mint.key(),
             ctx.accounts.attribute_src_token_account.key(),
@@ -330,7 +662,7 @@ pub fn harvest_crop(ctx: Context<HarvestCrop>, crop_slot: String) -> Result<()>
             ctx.accounts.field_mint.to_account_info(),
             ctx.accounts.field_metadata.to_account_info(),
             ctx.accounts.field_edition.to_account_info(),
-            ctx.accounts.user_account.to_account_info(),
+            holder_info,
             ctx.accounts.farms_pda.to_account_info(),
             ctx.accounts.attribute_mint.to_account_info(),
             ctx.accounts.attribute_src_token_account.to_account_info(),
@@ -346,123 +678,1184 @@ pub fn harvest_crop(ctx: Context<HarvestCrop>, crop_slot: String) -> Result<()>
         ],
     )?;
 
+    // With produce set up for its species, the ripe crop is burned and turned into fungible produce
+    if let Some(produce_info) = &produce {
+        let produce_mint = ctx
+            .accounts
+            .produce_mint
+            .as_ref()
+            .ok_or(FarmerHouseError::ProduceMismatch)?;
+        let produce_token_account = ctx
+            .accounts
+            .produce_token_account
+            .as_ref()
+            .ok_or(FarmerHouseError::ProduceMismatch)?;
+        let attribute_edition = ctx
+            .accounts
+            .attribute_edition
+            .as_ref()
+            .ok_or(FarmerHouseError::ProduceMismatch)?;
+        require_keys_eq!(
+            produce_mint.key(),
+            produce_info.mint,
+            FarmerHouseError::ProduceMismatch
+        );
+        require_keys_eq!(
+            produce_token_account.key(),
+            get_associated_token_address(crop_owner.key, &produce_mint.key()),
+            FarmerHouseError::ProduceMismatch
+        );
+
+        invoke(
+            &burn_nft(
+                TOKEN_METADATA_ID,
+                ctx.accounts.attribute_metadata.key(),
+                ctx.accounts.user_account.key(),
+                ctx.accounts.attribute_mint.key(),
+                ctx.accounts.attribute_dst_token_account.key(),
+                attribute_edition.key(),
+                token::ID,
+                None,
+            ),
+            &[
+                ctx.accounts.attribute_metadata.to_account_info(),
+                ctx.accounts.user_account.to_account_info(),
+                ctx.accounts.attribute_mint.to_account_info(),
+                ctx.accounts.attribute_dst_token_account.to_account_info(),
+                attribute_edition.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        let mut amount = produce_info.config.yield_of(grade);
+        if let Some(companions_info) = initialized::<Companions>(&ctx.accounts.companions)? {
+            amount = companions_info.yield_of(amount, &neighbours);
+        }
+        amount = soil.yield_of(amount);
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.user_account.to_account_info(),
+                associated_token: produce_token_account.to_account_info(),
+                authority: crop_owner.clone(),
+                mint: produce_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: produce_mint.to_account_info(),
+                    to: produce_token_account.to_account_info(),
+                    authority: ctx.accounts.farms_pda.to_account_info(),
+                },
+                &[&[
+                    b"farmer-house-farms",
+                    ctx.accounts.farmer_house_program.key().as_ref(),
+                    &ctx.accounts.farms_pda.instance_seed(),
+                    &[ctx.accounts.farms_pda.bump],
+                ]],
+            ),
+            amount,
+        )?;
+        msg!("Crop turned into {} produce", amount);
+        proceeds = produce_info.config.unit_price.saturating_mul(amount);
+
+        // The grade went into the yield, nothing is left to sell the crop by
+        ctx.accounts
+            .crop_state
+            .close(ctx.accounts.user_account.to_account_info())?;
+    }
+
+    if let Some(lease_info) = lease {
+        let spl_ata_source = ctx
+            .accounts
+            .spl_ata_source
+            .as_ref()
+            .ok_or(FarmerHouseError::LeaseMismatch)?;
+        let lessor_spl_ata = ctx
+            .accounts
+            .lessor_spl_ata
+            .as_ref()
+            .ok_or(FarmerHouseError::LeaseMismatch)?;
+        require_keys_eq!(
+            lessor_spl_ata.owner,
+            lease_info.owner,
+            FarmerHouseError::OwnerMismatch
+        );
+        require_keys_eq!(
+            lessor_spl_ata.mint,
+            ctx.accounts.farms_pda.spl_mint_address,
+            FarmerHouseError::MintMismatch
+        );
+
+        let share = lease_info.share_of(proceeds);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: spl_ata_source.to_account_info(),
+                    to: lessor_spl_ata.to_account_info(),
+                    authority: ctx.accounts.user_account.to_account_info(),
+                },
+            ),
+            share,
+        )?;
+        msg!("Paid {} to the owner of the field", share);
+    }
+
     Ok(())
 }
 
-#[derive(Accounts)]
-pub struct PlantSeed<'info> {
-    /// CHECK: Trifle will check this
-    #[account(mut)]
-    pub trifle_account: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
-    pub farms_pda: Box<Account<'info, FarmsPda>>,
-    #[account(mut)]
-    pub user_account: Signer<'info>,
-    /// CHECK: Trifle will check this
-    #[account(mut)]
-    pub escrow_constraint_model: UncheckedAccount<'info>,
-
-    /// CHECK: 
-    pub escrow_account: UncheckedAccount<'info>,
-    /// CHECK: 
-    #[account(mut)]
-    pub field_mint: Box<Account<'info, token::Mint>>,
-    /// CHECK: 
-    #[account(mut)]
-    pub field_token_account: UncheckedAccount<'info>,
-    /// CHECK: 
-    #[account(mut)]
-    pub field_master_edition: UncheckedAccount<'info>,
+/// Records the hash of the slot a crop committed to, which anyone can do once the slot has passed, before
+/// the SlotHashes sysvar drops it
+pub fn reveal_crop(ctx: Context<RevealCrop>) -> Result<()> {
+    require!(
+        ctx.accounts.crop_state.reveal_slot != 0,
+        FarmerHouseError::EventsMismatch
+    );
 
-    /// CHECK:
-    #[account(mut)]
-    pub attribute_mint: Box<Account<'info, token::Mint>>,
-    /// CHECK:
-    #[account(mut)]
-    pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
-    /// CHECK:
-    #[account(mut)]
-    pub attribute_dst_token_account: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut)]
-    pub attribute_metadata: UncheckedAccount<'info>,
+    match reveal(&mut ctx.accounts.crop_state, Some(&ctx.accounts.slot_hashes))? {
+        Some(_) => msg!("Crop revealed"),
+        None => msg!("Crop revealed too late, its slot hash is gone"),
+    }
 
-    /// CHECK: Metaplex will check this
-    pub token_metadata_program: UncheckedAccount<'info>,
-    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
-    pub token_program: Program<'info, token::Token>,
-    pub system_program: Program<'info, System>,
-    pub farmer_house_program: Program<'info, program_farmer_house>,
-    pub rent: Sysvar<'info, Rent>,
-    /// CHECK: Trifle will check this
-    pub trifle_program: UncheckedAccount<'info>,
+    Ok(())
 }
 
-#[derive(Accounts)]
-pub struct Water<'info> {
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
-    pub farms_pda: Account<'info, FarmsPda>,
+pub fn clear_slot(ctx: Context<ClearSlot>, crop_slot: String) -> Result<()> {
+    // Check for all program accounts (in case they're not being correctly checked on trifle)
+    require_eq!(
+        ctx.accounts.token_program.key(),
+        token::ID,
+        FarmerHouseError::ProgramMismatch
+    );
 
-    #[account(mut)]
-    pub user_account: Signer<'info>,
+    require_eq!(
+        ctx.accounts.associated_token_program.key(),
+        associated_token::ID,
+        FarmerHouseError::ProgramMismatch
+    );
 
-    #[account(mut)]
-    pub field_mint: Account<'info, token::Mint>,
-    #[account(mut)]
-    pub field_associated_token: Box<Account<'info, token::TokenAccount>>,
+    require_eq!(
+        ctx.accounts.token_metadata_program.key(),
+        TOKEN_METADATA_ID,
+        FarmerHouseError::ProgramMismatch
+    );
 
-    /// CHECK:
-    #[account(mut)]
-    pub crop_metadata: UncheckedAccount<'info>,
+    require_eq!(
+        ctx.accounts.trifle_program.key(),
+        TRIFLE_PROGRAM_ID,
+        FarmerHouseError::ProgramMismatch
+    );
 
-    /// CHECK: Trifle will check this
-    pub trifle_account: UncheckedAccount<'info>,
+    require_eq!(
+        ctx.accounts.farmer_house_program.key(),
+        program_farmer_house::id(),
+        FarmerHouseError::ProgramMismatch
+    );
 
-    /// CHECK: Metaplex will check this
-    pub token_metadata_program: UncheckedAccount<'info>,
-    pub farmer_house_program: Program<'info, program_farmer_house>,
-    pub system_program: Program<'info, System>,
-}
+    // Ripe crops never wither, they have to be harvested instead
+    let species = ctx
+        .accounts
+        .farms_pda
+        .species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
+    let metadata_info =
+        Metadata::from_account_info(&ctx.accounts.attribute_metadata.to_account_info());
+    require_keys_neq!(
+        species.ripe,
+        metadata_info
+            .as_ref()
+            .unwrap()
+            .collection
+            .as_ref()
+            .unwrap()
+            .key,
+        FarmerHouseError::CropReady
+    );
 
-#[derive(Accounts)]
-pub struct HarvestCrop<'info> {
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(mut)]
-    pub trifle_account: UncheckedAccount<'info>,
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
-    pub farms_pda: Box<Account<'info, FarmsPda>>,
-    #[account(mut)]
-    pub user_account: Signer<'info>,
+    let growth_config = ctx
+        .accounts
+        .farms_pda
+        .growth_config
+        .with_tool(ctx.accounts.crop_state.tool_equipped);
 
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(mut)]
-    pub escrow_constraint_model: UncheckedAccount<'info>,
+    require!(
+        ctx.accounts
+            .crop_state
+            .is_withered(Clock::get()?.unix_timestamp, growth_config.wither_deadline),
+        FarmerHouseError::CropNotWithered
+    );
 
-    /// CHECK: We love pain.
-    pub escrow_account: UncheckedAccount<'info>,
-    /// CHECK: We love pain.
-    #[account(mut)]
-    pub field_mint: Box<Account<'info, token::Mint>>,
-    /// CHECK: We love pain.
-    #[account(mut)]
-    pub field_token_account: Box<Account<'info, token::TokenAccount>>,
-    /// CHECK: We love pain.
-    #[account(mut)]
+    *ctx.accounts.field_state.slot_mut(&crop_slot)? = FieldSlot::default();
+
+    // A tenant, farmhand or co-op member clears the slot through the vault holding the field, the
+    // withered crop still goes to their wallet to be burned
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::HARVEST,
+    )?;
+    let holder_info = holder.fund(
+        &ctx.accounts.user_account,
+        ctx.accounts
+            .lease_vault
+            .as_ref()
+            .or(ctx.accounts.farmhand_vault.as_ref())
+            .or(ctx.accounts.coop_vault.as_ref()),
+        &ctx.accounts.system_program,
+    )?;
+
+    holder.invoke(
+        &trifle_instruction::transfer_out(
+            TRIFLE_PROGRAM_ID,
+            ctx.accounts.trifle_account.key(),
+            ctx.accounts.escrow_constraint_model.key(),
+            ctx.accounts.escrow_account.key(),
+            ctx.accounts.field_token_account.key(),
+            ctx.accounts.field_mint.key(),
+            ctx.accounts.field_metadata.key(),
+            Some(ctx.accounts.field_edition.key()),
+            holder.key,
+            ctx.accounts.farms_pda.key(),
+            ctx.accounts.attribute_mint.key(),
+            ctx.accounts.attribute_src_token_account.key(),
+            ctx.accounts.attribute_dst_token_account.key(),
+            ctx.accounts.attribute_metadata.key(),
+            crop_slot,
+            1,
+        ),
+        &[
+            ctx.accounts.trifle_account.to_account_info(),
+            ctx.accounts.escrow_constraint_model.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.field_token_account.to_account_info(),
+            ctx.accounts.field_mint.to_account_info(),
+            ctx.accounts.field_metadata.to_account_info(),
+            ctx.accounts.field_edition.to_account_info(),
+            holder_info,
+            ctx.accounts.farms_pda.to_account_info(),
+            ctx.accounts.attribute_mint.to_account_info(),
+            ctx.accounts.attribute_src_token_account.to_account_info(),
+            ctx.accounts.attribute_dst_token_account.to_account_info(),
+            ctx.accounts.attribute_metadata.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.trifle_program.to_account_info(),
+            ctx.accounts.instructions.to_account_info(),
+        ],
+    )?;
+
+    // Burn the withered crop now that it is back in the user's wallet
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.attribute_mint.to_account_info(),
+                from: ctx.accounts.attribute_dst_token_account.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+pub fn fertilize(ctx: Context<Fertilize>, crop_slot: String) -> Result<()> {
+    let farms_pda_info = &ctx.accounts.farms_pda;
+
+    require_eq!(
+        ctx.accounts.token_program.key(),
+        token::ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    require_eq!(
+        ctx.accounts.fertilizer_mint.key(),
+        farms_pda_info.fertilizer_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+
+    // Confirm user is owner of the field the crop is planted in, or tends it through the lease, farmhand
+    // or co-op whose vault holds it
+    require_eq!(
+        ctx.accounts.field_associated_token.mint,
+        ctx.accounts.field_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::WATER,
+    )?;
+    require_keys_eq!(
+        ctx.accounts.field_associated_token.owner,
+        holder.key,
+        FarmerHouseError::OwnerMismatch
+    );
+
+    assert_derivation(
+        &TRIFLE_PROGRAM_ID,
+        &ctx.accounts.trifle_account,
+        &[
+            TRIFLE_SEED.as_bytes(),
+            ctx.accounts.field_mint.key().as_ref(),
+            ctx.accounts.farms_pda.key().as_ref(),
+        ],
+    )?;
+
+    let trifle = Trifle::from_account_info(&ctx.accounts.trifle_account.to_account_info())?;
+    let planted = trifle
+        .tokens
+        .get(&crop_slot)
+        .and_then(|tokens| tokens.first())
+        .ok_or(FarmerHouseError::TrifleMismatch)?;
+    require_eq!(
+        planted.mint,
+        ctx.accounts.crop_mint.key(),
+        FarmerHouseError::TrifleMismatch
+    );
+
+    // A ripe crop is done growing, fertilizer would only push back when it can be watered
+    require!(
+        ctx.accounts.field_state.slot(&crop_slot)?.stage != Some(CropStage::Ripe),
+        FarmerHouseError::CropReady
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let crop_state_info = &mut ctx.accounts.crop_state;
+    let growth_config = farms_pda_info
+        .growth_config
+        .with_tool(crop_state_info.tool_equipped);
+
+    require!(
+        !crop_state_info.is_withered(now, growth_config.wither_deadline),
+        FarmerHouseError::CropWithered
+    );
+    require!(
+        crop_state_info.ready_at > now,
+        FarmerHouseError::CropNotGrowing
+    );
+
+    crop_state_info.ready_at = (crop_state_info.ready_at
+        - growth_config.fertilizer_boost)
+        .max(now);
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.fertilizer_mint.to_account_info(),
+                from: ctx.accounts.fertilizer_token_account.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+pub fn equip_tool(ctx: Context<EquipTool>) -> Result<()> {
+    // Check for all program accounts (in case they're not being correctly checked on trifle)
+    require_eq!(
+        ctx.accounts.token_metadata_program.key(),
+        TOKEN_METADATA_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    require_eq!(
+        ctx.accounts.trifle_program.key(),
+        TRIFLE_PROGRAM_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    let metadata_info =
+        Metadata::from_account_info(&ctx.accounts.attribute_metadata.to_account_info());
+    require_eq!(
+        ctx.accounts.farms_pda.tool_collection_address,
+        metadata_info
+            .as_ref()
+            .unwrap()
+            .collection
+            .as_ref()
+            .unwrap()
+            .key,
+        FarmerHouseError::CollectionMismatch
+    );
+
+    // A tenant, farmhand or co-op member equips the tool through the vault holding the field, like
+    // planting a seed
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::PLANT,
+    )?;
+    let holder_info = holder.fund(
+        &ctx.accounts.user_account,
+        ctx.accounts
+            .lease_vault
+            .as_ref()
+            .or(ctx.accounts.farmhand_vault.as_ref())
+            .or(ctx.accounts.coop_vault.as_ref()),
+        &ctx.accounts.system_program,
+    )?;
+    if holder.is_vault() {
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Approve {
+                    to: ctx.accounts.attribute_src_token_account.to_account_info(),
+                    delegate: holder_info.clone(),
+                    authority: ctx.accounts.user_account.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+    }
+
+    holder.invoke(
+        &trifle_instruction::transfer_in(
+            TRIFLE_PROGRAM_ID,
+            ctx.accounts.trifle_account.key(),
+            ctx.accounts.farms_pda.key(),
+            holder.key,
+            ctx.accounts.escrow_constraint_model.key(),
+            ctx.accounts.escrow_account.key(),
+            Some(ctx.accounts.field_mint.key()),
+            Some(ctx.accounts.field_token_account.key()),
+            Some(ctx.accounts.field_master_edition.key()),
+            ctx.accounts.attribute_mint.key(),
+            ctx.accounts.attribute_src_token_account.key(),
+            Some(ctx.accounts.attribute_dst_token_account.key()),
+            Some(ctx.accounts.attribute_metadata.key()),
+            None,
+            None,
+            TOOL_SLOT.to_string(),
+            1,
+        ),
+        &[
+            ctx.accounts.trifle_account.to_account_info(),
+            ctx.accounts.farms_pda.to_account_info(),
+            holder_info,
+            ctx.accounts.escrow_constraint_model.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.field_mint.to_account_info(),
+            ctx.accounts.field_token_account.to_account_info(),
+            ctx.accounts.field_master_edition.to_account_info(),
+            ctx.accounts.attribute_mint.to_account_info(),
+            ctx.accounts.attribute_src_token_account.to_account_info(),
+            ctx.accounts.attribute_dst_token_account.to_account_info(),
+            ctx.accounts.attribute_metadata.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.trifle_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+pub fn unequip_tool(ctx: Context<UnequipTool>) -> Result<()> {
+    // Check for all program accounts (in case they're not being correctly checked on trifle)
+    require_eq!(
+        ctx.accounts.token_metadata_program.key(),
+        TOKEN_METADATA_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    require_eq!(
+        ctx.accounts.trifle_program.key(),
+        TRIFLE_PROGRAM_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    // A tenant, farmhand or co-op member takes the tool out through the vault holding the field, like
+    // harvesting a crop
+    let holder = FieldHolder::of(
+        &ctx.accounts.user_account.key(),
+        ctx.accounts.lease.as_deref(),
+        ctx.accounts.farmhand.as_deref(),
+        ctx.accounts.coop.as_deref(),
+        Farmhand::HARVEST,
+    )?;
+    let holder_info = holder.fund(
+        &ctx.accounts.user_account,
+        ctx.accounts
+            .lease_vault
+            .as_ref()
+            .or(ctx.accounts.farmhand_vault.as_ref())
+            .or(ctx.accounts.coop_vault.as_ref()),
+        &ctx.accounts.system_program,
+    )?;
+
+    holder.invoke(
+        &trifle_instruction::transfer_out(
+            TRIFLE_PROGRAM_ID,
+            ctx.accounts.trifle_account.key(),
+            ctx.accounts.escrow_constraint_model.key(),
+            ctx.accounts.escrow_account.key(),
+            ctx.accounts.field_token_account.key(),
+            ctx.accounts.field_mint.key(),
+            ctx.accounts.field_metadata.key(),
+            Some(ctx.accounts.field_edition.key()),
+            holder.key,
+            ctx.accounts.farms_pda.key(),
+            ctx.accounts.attribute_mint.key(),
+            ctx.accounts.attribute_src_token_account.key(),
+            ctx.accounts.attribute_dst_token_account.key(),
+            ctx.accounts.attribute_metadata.key(),
+            TOOL_SLOT.to_string(),
+            1,
+        ),
+        &[
+            ctx.accounts.trifle_account.to_account_info(),
+            ctx.accounts.escrow_constraint_model.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.field_token_account.to_account_info(),
+            ctx.accounts.field_mint.to_account_info(),
+            ctx.accounts.field_metadata.to_account_info(),
+            ctx.accounts.field_edition.to_account_info(),
+            holder_info,
+            ctx.accounts.farms_pda.to_account_info(),
+            ctx.accounts.attribute_mint.to_account_info(),
+            ctx.accounts.attribute_src_token_account.to_account_info(),
+            ctx.accounts.attribute_dst_token_account.to_account_info(),
+            ctx.accounts.attribute_metadata.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.associated_token_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.trifle_program.to_account_info(),
+            ctx.accounts.instructions.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Token Metadata rejects newly verified creators unless they sign, so only creators that were already
+// verified, and the farms PDA signing the update, keep their verification
+fn stage_creators_of(
+    stage_creators: &[StageCreator],
+    current_creators: &Option<Vec<Creator>>,
+    update_authority: &Pubkey,
+) -> Vec<Creator> {
+    stage_creators
+        .iter()
+        .map(|stage_creator| Creator {
+            address: stage_creator.address,
+            verified: stage_creator.address == *update_authority
+                || current_creators.iter().flatten().any(|creator| {
+                    creator.address == stage_creator.address && creator.verified
+                }),
+            share: stage_creator.share,
+        })
+        .collect()
+}
+
+/// Overwrites the metadata of a crop with the data of a stage
+fn apply_stage_data(
+    data: &mut DataV2,
+    stage_data: &StageMetadataData,
+    current_creators: &Option<Vec<Creator>>,
+    update_authority: &Pubkey,
+) {
+    data.name = stage_data.name.clone();
+    data.symbol = stage_data.symbol.clone();
+    data.uri = stage_data.uri.clone();
+    data.seller_fee_basis_points = stage_data.seller_fee_basis_points;
+    if let Some(stage_creators) = &stage_data.creators {
+        data.creators = Some(stage_creators_of(
+            stage_creators,
+            current_creators,
+            update_authority,
+        ));
+    }
+}
+
+/// The hash of the slot the crop committed to, recorded by the first call after the slot so it stays
+/// known once the SlotHashes sysvar drops it, none when it was dropped before. Fails until the slot has
+/// passed.
+fn reveal(
+    crop_state_info: &mut CropState,
+    slot_hashes_info: Option<&UncheckedAccount>,
+) -> Result<Option<[u8; 32]>> {
+    if crop_state_info.revealed_hash.is_some() {
+        return Ok(crop_state_info.revealed_hash);
+    }
+
+    let slot_hashes_info = slot_hashes_info.ok_or(FarmerHouseError::EventsMismatch)?;
+    require_keys_eq!(
+        slot_hashes_info.key(),
+        slot_hashes::ID,
+        FarmerHouseError::EventsMismatch
+    );
+    require!(
+        Clock::get()?.slot > crop_state_info.reveal_slot,
+        FarmerHouseError::CropNotRevealed
+    );
+
+    crop_state_info.revealed_hash = revealed_hash(slot_hashes_info, crop_state_info.reveal_slot)?;
+    Ok(crop_state_info.revealed_hash)
+}
+
+/// A roll between 0 and 9999 drawn from a revealed hash and `keys`
+fn roll_of(hash: &[u8; 32], keys: &[&[u8]]) -> u64 {
+    let mut seeds = vec![&hash[..]];
+    seeds.extend_from_slice(keys);
+    let seed = hashv(&seeds).to_bytes();
+    let mut roll = [0u8; 8];
+    roll.copy_from_slice(&seed[..8]);
+    u64::from_le_bytes(roll) % 10000
+}
+
+/// The hash of the first slot from `reveal_slot` on, none once the SlotHashes sysvar has dropped it
+fn revealed_hash(slot_hashes_info: &AccountInfo, reveal_slot: u64) -> Result<Option<[u8; 32]>> {
+    let data = slot_hashes_info.try_borrow_data()?;
+    let read_u64 = |offset: usize| -> Result<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(
+            data.get(offset..offset + 8)
+                .ok_or(FarmerHouseError::EventsMismatch)?,
+        );
+        Ok(u64::from_le_bytes(bytes))
+    };
+
+    // The sysvar holds the number of entries, then a slot and its hash for each, latest first
+    let mut revealed = None;
+    for index in 0..read_u64(0)? as usize {
+        let offset = 8 + index * 40;
+        let slot = read_u64(offset)?;
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(
+            data.get(offset + 8..offset + 40)
+                .ok_or(FarmerHouseError::EventsMismatch)?,
+        );
+
+        if slot == reveal_slot {
+            return Ok(Some(hash));
+        }
+        if slot < reveal_slot {
+            return Ok(revealed);
+        }
+        revealed = Some(hash);
+    }
+
+    Ok(None)
+}
+
+/// Whether a tool is equipped in the tool slot of the field's Trifle
+pub(crate) fn tool_equipped(trifle: &Trifle) -> bool {
+    matches!(
+        trifle.tokens.get(TOOL_SLOT),
+        Some(tokens) if tokens.iter().any(|token| token.amount > 0)
+    )
+}
+
+#[derive(Accounts)]
+pub struct PlantSeed<'info> {
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub trifle_account: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+
+    /// CHECK: 
+    pub escrow_account: UncheckedAccount<'info>,
+    /// CHECK: 
+    #[account(mut)]
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: 
+    #[account(mut)]
+    pub field_token_account: UncheckedAccount<'info>,
+    /// CHECK: 
+    #[account(mut)]
+    pub field_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK:
+    #[account(mut)]
+    pub attribute_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK:
+    #[account(mut)]
+    pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK:
+    #[account(mut)]
+    pub attribute_dst_token_account: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub attribute_metadata: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        space = CropState::SPACE,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), attribute_mint.key().as_ref()],
+        bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    /// CHECK: The Events PDA, set or not
+    #[account(seeds = [b"farmer-house-events", farms_pda.key().as_ref()], bump)]
+    pub events: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    /// CHECK: plant_seed checks this is the vault of the lease, only needed with lease
+    #[account(mut)]
+    pub lease_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    /// CHECK: plant_seed checks this is the vault of the farmhand, only needed with farmhand
+    #[account(mut)]
+    pub farmhand_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: plant_seed checks this is the vault of the co-op, only needed with coop
+    #[account(mut)]
+    pub coop_vault: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        space = FieldState::SPACE,
+        seeds = [b"farmer-house-field", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump
+    )]
+    pub field_state: Box<Account<'info, FieldState>>,
+
+    /// CHECK: Metaplex will check this
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Trifle will check this
+    pub trifle_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Water<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(mut)]
+    pub field_mint: Account<'info, token::Mint>,
+    #[account(mut)]
+    pub field_associated_token: Box<Account<'info, token::TokenAccount>>,
+
+    pub crop_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK:
+    #[account(mut)]
+    pub crop_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), crop_mint.key().as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(mut, seeds = [b"farmer-house-field", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = field_state.bump)]
+    pub field_state: Box<Account<'info, FieldState>>,
+    /// CHECK: water checks this is the StageMetadata PDA of the next stage, set or not
+    pub stage_metadata: UncheckedAccount<'info>,
+    /// CHECK: The Companions PDA of the species, set or not
+    #[account(seeds = [b"farmer-house-companions", farms_pda.key().as_ref(), &[crop_state.species_id]], bump)]
+    pub companions: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"farmer-house-mutation", farms_pda.key().as_ref(), &[crop_state.species_id]], bump = mutation.bump)]
+    pub mutation: Option<Box<Account<'info, Mutation>>>,
+    /// CHECK: water checks this is the SlotHashes sysvar, only needed with mutation
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+
+    /// CHECK: Trifle will check this
+    pub trifle_account: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex will check this
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestCrop<'info> {
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub trifle_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+
+    /// CHECK: We love pain.
+    pub escrow_account: UncheckedAccount<'info>,
+    /// CHECK: We love pain.
+    #[account(mut)]
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: We love pain.
+    #[account(mut)]
+    pub field_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: We love pain.
+    #[account(mut)]
     pub field_metadata: UncheckedAccount<'info>,
     /// CHECK: We love pain.
     #[account(mut)]
     pub field_edition: UncheckedAccount<'info>,
 
     /// CHECK:
+    #[account(mut)]
     pub attribute_mint: Box<Account<'info, token::Mint>>,
     /// CHECK:
     #[account(mut)]
     pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: Metaplex will check this, harvest_crop creates it first when a vault holds the field
+    #[account(mut)]
+    pub attribute_dst_token_account: UncheckedAccount<'info>,
     /// CHECK:
     #[account(mut)]
+    pub attribute_metadata: UncheckedAccount<'info>,
+    /// CHECK: Metaplex will check this, only needed to burn a crop that yields produce
+    #[account(mut)]
+    pub attribute_edition: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), attribute_mint.key().as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(mut, seeds = [b"farmer-house-field", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = field_state.bump)]
+    pub field_state: Box<Account<'info, FieldState>>,
+    /// CHECK: The Produce PDA of the species, set or not
+    #[account(seeds = [b"farmer-house-produce", farms_pda.key().as_ref(), &[crop_state.species_id]], bump)]
+    pub produce: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub produce_mint: Option<Box<Account<'info, token::Mint>>>,
+    /// CHECK: harvest_crop checks this is the ATA of the produce mint and whoever gets the crop, creating
+    /// it if needed
+    #[account(mut)]
+    pub produce_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: The Companions PDA of the species, set or not
+    #[account(seeds = [b"farmer-house-companions", farms_pda.key().as_ref(), &[crop_state.species_id]], bump)]
+    pub companions: UncheckedAccount<'info>,
+    /// CHECK: The Events PDA, set or not
+    #[account(seeds = [b"farmer-house-events", farms_pda.key().as_ref()], bump)]
+    pub events: UncheckedAccount<'info>,
+    /// CHECK: harvest_crop checks this is the SlotHashes sysvar, only needed by crops planted with events
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-mutation", farms_pda.key().as_ref(), &[crop_state.species_id]], bump = mutation.bump)]
+    pub mutation: Option<Box<Account<'info, Mutation>>>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    /// CHECK: harvest_crop checks this is the vault of the lease, only needed with lease
+    #[account(mut)]
+    pub lease_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    /// CHECK: harvest_crop checks this is the vault of the farmhand, only needed with farmhand
+    #[account(mut)]
+    pub farmhand_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: harvest_crop checks this is the owner who delegated the field, only needed with farmhand
+    pub farmhand_owner: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: harvest_crop checks this is the vault of the co-op, only needed with coop
+    #[account(mut)]
+    pub coop_vault: Option<UncheckedAccount<'info>>,
+    /// The tenant's currency token account paying the owner's share, only needed with lease
+    #[account(mut)]
+    pub spl_ata_source: Option<Box<Account<'info, token::TokenAccount>>>,
+    /// The owner's currency token account, only needed with lease
+    #[account(mut)]
+    pub lessor_spl_ata: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    /// CHECK: Metaplex will check this
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Trifle will check this
+    pub trifle_program: UncheckedAccount<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealCrop<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(
+        mut,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), crop_state.crop_mint.as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    /// CHECK: reveal_crop checks this is the SlotHashes sysvar
+    pub slot_hashes: UncheckedAccount<'info>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct ClearSlot<'info> {
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub trifle_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+
+    /// CHECK: Trifle will check this
+    pub escrow_account: UncheckedAccount<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_metadata: UncheckedAccount<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub attribute_mint: Box<Account<'info, token::Mint>>,
+    #[account(mut)]
+    pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut)]
     pub attribute_dst_token_account: Box<Account<'info, token::TokenAccount>>,
-    /// CHECK:
+    /// CHECK: Trifle will check this
+    pub attribute_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), attribute_mint.key().as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(mut, seeds = [b"farmer-house-field", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = field_state.bump)]
+    pub field_state: Box<Account<'info, FieldState>>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    /// CHECK: clear_slot checks this is the vault of the lease, only needed with lease
+    #[account(mut)]
+    pub lease_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    /// CHECK: clear_slot checks this is the vault of the farmhand, only needed with farmhand
+    #[account(mut)]
+    pub farmhand_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: clear_slot checks this is the vault of the co-op, only needed with coop
+    #[account(mut)]
+    pub coop_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex will check this
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Trifle will check this
+    pub trifle_program: UncheckedAccount<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Fertilize<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    pub user_account: Signer<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    pub field_associated_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// CHECK: Checked against the trifle derivation
+    pub trifle_account: UncheckedAccount<'info>,
+
+    pub crop_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), crop_mint.key().as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Box<Account<'info, CropState>>,
+    #[account(seeds = [b"farmer-house-field", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = field_state.bump)]
+    pub field_state: Box<Account<'info, FieldState>>,
+
+    #[account(mut)]
+    pub fertilizer_mint: Box<Account<'info, token::Mint>>,
+    #[account(mut)]
+    pub fertilizer_token_account: Box<Account<'info, token::TokenAccount>>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct EquipTool<'info> {
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub trifle_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+
+    /// CHECK: Trifle will check this
+    pub escrow_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_token_account: UncheckedAccount<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub attribute_mint: Box<Account<'info, token::Mint>>,
+    #[account(mut)]
+    pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub attribute_dst_token_account: UncheckedAccount<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub attribute_metadata: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    /// CHECK: equip_tool checks this is the vault of the lease, only needed with lease
+    #[account(mut)]
+    pub lease_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    /// CHECK: equip_tool checks this is the vault of the farmhand, only needed with farmhand
+    #[account(mut)]
+    pub farmhand_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: equip_tool checks this is the vault of the co-op, only needed with coop
+    #[account(mut)]
+    pub coop_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex will check this
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Trifle will check this
+    pub trifle_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnequipTool<'info> {
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub trifle_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+
+    /// CHECK: Trifle will check this
+    pub escrow_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    #[account(mut)]
+    pub field_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_metadata: UncheckedAccount<'info>,
+    /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub field_edition: UncheckedAccount<'info>,
+
+    pub attribute_mint: Box<Account<'info, token::Mint>>,
+    #[account(mut)]
+    pub attribute_src_token_account: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut)]
+    pub attribute_dst_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: Trifle will check this
     pub attribute_metadata: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = lease.bump)]
+    pub lease: Option<Box<Account<'info, Lease>>>,
+    /// CHECK: unequip_tool checks this is the vault of the lease, only needed with lease
+    #[account(mut)]
+    pub lease_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = farmhand.bump)]
+    pub farmhand: Option<Box<Account<'info, Farmhand>>>,
+    /// CHECK: unequip_tool checks this is the vault of the farmhand, only needed with farmhand
+    #[account(mut)]
+    pub farmhand_vault: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), field_mint.key().as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: unequip_tool checks this is the vault of the co-op, only needed with coop
+    #[account(mut)]
+    pub coop_vault: Option<UncheckedAccount<'info>>,
 
     /// CHECK: Metaplex will check this
     pub token_metadata_program: UncheckedAccount<'info>,
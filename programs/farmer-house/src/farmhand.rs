@@ -0,0 +1,204 @@
+use {
+    crate::{errors::*, program::FarmerHouse as program_farmer_house, Farmhand, FarmsPda},
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{associated_token, token},
+    mpl_token_metadata::{
+        state::{Metadata, TokenMetadataAccount},
+        ID as TOKEN_METADATA_ID,
+    },
+};
+
+/// Lets `delegate` take `actions` on a field until `expires_at`, moving the field into the vault of the
+/// farmhand so it can sign the Trifle transfers in place of the owner
+pub fn delegate_farmhand(
+    ctx: Context<DelegateFarmhand>,
+    delegate: Pubkey,
+    actions: u8,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        actions != 0
+            && actions & !Farmhand::ALL_ACTIONS == 0
+            && expires_at > Clock::get()?.unix_timestamp,
+        FarmerHouseError::InvalidFarmhand
+    );
+
+    let metadata_info = Metadata::from_account_info(&ctx.accounts.field_metadata.to_account_info())?;
+    require!(
+        matches!(
+            &metadata_info.collection,
+            Some(collection) if collection.key == ctx.accounts.farms_pda.field_collection_address
+        ),
+        FarmerHouseError::CollectionMismatch
+    );
+    require_keys_eq!(
+        metadata_info.mint,
+        ctx.accounts.field_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+
+    let farmhand_info = &mut ctx.accounts.farmhand;
+    farmhand_info.bump = *ctx.bumps.get("farmhand").unwrap();
+    farmhand_info.vault_bump = *ctx.bumps.get("farmhand_vault").unwrap();
+    farmhand_info.field_mint = ctx.accounts.field_mint.key();
+    farmhand_info.owner = ctx.accounts.user_account.key();
+    farmhand_info.delegate = delegate;
+    farmhand_info.actions = actions;
+    farmhand_info.expires_at = expires_at;
+
+    // Transfer fieldNFT from ATA of user to ATA of the farmhand vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+/// Returns the field to its owner, along with what is left in the vault. Crops still in the field stay
+/// with it.
+pub fn revoke_farmhand(ctx: Context<RevokeFarmhand>) -> Result<()> {
+    let farmhand_info = &ctx.accounts.farmhand;
+    require_keys_eq!(
+        farmhand_info.owner,
+        ctx.accounts.user_account.key(),
+        FarmerHouseError::OwnerMismatch
+    );
+
+    let farmhand = farmhand_info.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"farmer-house-farmhand-vault",
+        farmhand.as_ref(),
+        &[farmhand_info.vault_bump],
+    ];
+
+    // Transfer fieldNFT from ATA of the farmhand vault back to ATA of the owner
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.farmhand_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.field_ata_source.to_account_info(),
+            destination: ctx.accounts.user_account.to_account_info(),
+            authority: ctx.accounts.farmhand_vault.to_account_info(),
+        },
+        &[vault_seeds],
+    ))?;
+
+    let float = ctx.accounts.farmhand_vault.lamports();
+    if float > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.farmhand_vault.to_account_info(),
+                    to: ctx.accounts.user_account.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            float,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DelegateFarmhand<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: delegate_farmhand checks this is the metadata of the field
+    #[account(owner = TOKEN_METADATA_ID)]
+    pub field_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init,
+        payer = user_account,
+        space = Farmhand::SPACE,
+        seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump
+    )]
+    pub farmhand: Box<Account<'info, Farmhand>>,
+    #[account(seeds = [b"farmer-house-farmhand-vault", farmhand.key().as_ref()], bump)]
+    pub farmhand_vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = farmhand_vault
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeFarmhand<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-farmhand", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump = farmhand.bump
+    )]
+    pub farmhand: Box<Account<'info, Farmhand>>,
+    #[account(mut, seeds = [b"farmer-house-farmhand-vault", farmhand.key().as_ref()], bump = farmhand.vault_bump)]
+    pub farmhand_vault: SystemAccount<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = farmhand_vault
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
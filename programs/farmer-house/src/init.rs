@@ -1,13 +1,21 @@
 use {
-    crate::{errors::*, program::FarmerHouse as program_farmer_house, FarmsPda},
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, CompanionEffect, Companions, CropStage,
+        EventConfig, Events, FarmsPda, GrowthConfig, Mutation, MutationConfig, Produce, ProduceConfig, Recipe, RecipeConfig, ShopPrices, Species, StageMetadata, StageMetadataData, CROP_SLOTS,
+        FARMS_PDA_VERSION, MAX_COMPANIONS, MAX_RECIPE_INPUTS, TOMATO_SPECIES_ID, TOOL_SLOT,
+    },
     anchor_lang::{
         prelude::*,
+        system_program, Discriminator,
         solana_program::{
             program::{invoke, invoke_signed},
             sysvar::instructions::ID as INSTRUCTIONS_ID,
         },
     },
     anchor_spl::{associated_token, token},
+    mpl_token_metadata::state::{
+        MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+    },
     mpl_trifle::{
         instruction as trifle_instruction,
         // state::{trifle::Trifle, SolanaAccount, TRIFLE_SEED},
@@ -17,6 +25,7 @@ use {
 
 pub fn init_farms_pda(
     ctx: Context<InitFarmsPda>,
+    instance_id: u16,
     tomato_seed_collection_address: Pubkey,
     tomato_sappling_collection_address: Pubkey,
     ripe_tomato_collection_address: Pubkey,
@@ -31,6 +40,10 @@ pub fn init_farms_pda(
     farms_pda_info.tomato_sappling_collection_address = tomato_sappling_collection_address;
     farms_pda_info.ripe_tomato_collection_address = ripe_tomato_collection_address;
     farms_pda_info.field_collection_address = field_collection_address;
+    farms_pda_info.growth_config = GrowthConfig::default();
+    farms_pda_info.prices = ShopPrices::default();
+    farms_pda_info.instance_id = instance_id;
+    farms_pda_info.version = FARMS_PDA_VERSION;
 
     associated_token::create(CpiContext::new(
         ctx.accounts.associated_token_program.to_account_info(),
@@ -70,12 +83,6 @@ pub fn init_constraint_model(
         FarmerHouseError::ProgramMismatch
     );
 
-    require_eq!(
-        ctx.accounts.tomato_seed_collection_mint.key(),
-        farms_pda_info.tomato_seed_collection_address,
-        FarmerHouseError::CollectionMismatch
-    );
-
     msg!("Creating constraint model account");
 
     invoke(
@@ -96,17 +103,17 @@ pub fn init_constraint_model(
         ],
     )?;
 
-    let array = vec!["a1", "a2", "a3", "b1", "b2", "b3"];
-
-    array.iter().for_each(|&x| {
+    // Trifle slots accept a single collection, and only a verified one, but a crop moves through one
+    // unverified collection per stage and per species. Slots take the tokens the PAW verifiably created
+    // instead, so nothing else can be transferred in around plant_seed, which checks the collection.
+    CROP_SLOTS.iter().for_each(|&x| {
         match invoke_signed(
-            &trifle_instruction::add_collection_constraint_to_escrow_constraint_model(
+            &trifle_instruction::add_first_creator_constraint_to_escrow_constraint_model(
                 &TRIFLE_PROGRAM_ID,
                 &ctx.accounts.escrow_constraint_model.key(),
                 &ctx.accounts.pda_authority.key(),
                 &ctx.accounts.farms_pda.key(),
-                &ctx.accounts.tomato_seed_collection_mint.key(),
-                &ctx.accounts.tomato_seed_collection_metadata.key(),
+                &ctx.accounts.pda_authority.key(),
                 x.to_string(),
                 1,
                 1,
@@ -115,16 +122,14 @@ pub fn init_constraint_model(
                 ctx.accounts.escrow_constraint_model.to_account_info(),
                 ctx.accounts.pda_authority.to_account_info(),
                 ctx.accounts.farms_pda.to_account_info(),
-                ctx.accounts.tomato_seed_collection_mint.to_account_info(),
-                ctx.accounts
-                    .tomato_seed_collection_metadata
-                    .to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
                 ctx.accounts.trifle_program.to_account_info(),
                 ctx.accounts.instructions.to_account_info(),
             ],
             &[&[
                 b"farmer-house-farms",
                 ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
                 &[ctx.accounts.farms_pda.bump],
             ]],
         ) {
@@ -136,18 +141,522 @@ pub fn init_constraint_model(
     Ok(())
 }
 
+pub fn add_tool_slot(ctx: Context<AddToolSlot>) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require_eq!(
+        ctx.accounts.instructions.key(),
+        INSTRUCTIONS_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    require_eq!(
+        ctx.accounts.trifle_program.key(),
+        TRIFLE_PROGRAM_ID,
+        FarmerHouseError::ProgramMismatch
+    );
+
+    ctx.accounts.farms_pda.tool_collection_address = ctx.accounts.tool_collection_mint.key();
+
+    msg!("Adding tool slot to constraint model");
+
+    // A single slot holding one token of the tools collection
+    invoke_signed(
+        &trifle_instruction::add_collection_constraint_to_escrow_constraint_model(
+            &TRIFLE_PROGRAM_ID,
+            &ctx.accounts.escrow_constraint_model.key(),
+            &ctx.accounts.pda_authority.key(),
+            &ctx.accounts.farms_pda.key(),
+            &ctx.accounts.tool_collection_mint.key(),
+            &ctx.accounts.tool_collection_metadata.key(),
+            TOOL_SLOT.to_string(),
+            1,
+            1,
+        ),
+        &[
+            ctx.accounts.escrow_constraint_model.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.farms_pda.to_account_info(),
+            ctx.accounts.tool_collection_mint.to_account_info(),
+            ctx.accounts.tool_collection_metadata.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.trifle_program.to_account_info(),
+            ctx.accounts.instructions.to_account_info(),
+        ],
+        &[&[
+            b"farmer-house-farms",
+            ctx.accounts.farmer_house_program.key().as_ref(),
+            &ctx.accounts.farms_pda.instance_seed(),
+            &[ctx.accounts.farms_pda.bump],
+        ]],
+    )?;
+
+    Ok(())
+}
+
+pub fn register_species(
+    ctx: Context<RegisterSpecies>,
+    species_id: u8,
+    seed_collection_address: Pubkey,
+    sapling_collection_address: Pubkey,
+    ripe_collection_address: Pubkey,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    // The tomato is built into FarmsPda
+    require_neq!(
+        species_id,
+        TOMATO_SPECIES_ID,
+        FarmerHouseError::InvalidSpecies
+    );
+
+    let species_info = &mut ctx.accounts.species;
+
+    species_info.bump = *ctx.bumps.get("species").unwrap();
+    species_info.species_id = species_id;
+    species_info.seed_collection_address = seed_collection_address;
+    species_info.sapling_collection_address = sapling_collection_address;
+    species_info.ripe_collection_address = ripe_collection_address;
+
+    Ok(())
+}
+
+pub fn set_stage_metadata(
+    ctx: Context<SetStageMetadata>,
+    species_id: u8,
+    stage: CropStage,
+    data: StageMetadataData,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    // Crops are planted as seeds, so only the stages watering grows them to have metadata to apply
+    require!(
+        stage != CropStage::Seed,
+        FarmerHouseError::InvalidStageMetadata
+    );
+
+    require!(
+        valid_stage_data(&data, &ctx.accounts.farms_pda.authority_address),
+        FarmerHouseError::InvalidStageMetadata
+    );
+
+    let stage_metadata_info = &mut ctx.accounts.stage_metadata;
+
+    stage_metadata_info.bump = *ctx.bumps.get("stage_metadata").unwrap();
+    stage_metadata_info.species_id = species_id;
+    stage_metadata_info.stage = stage;
+    stage_metadata_info.data = data;
+
+    Ok(())
+}
+
+/// Whether Token Metadata accepts the data of a stage, its creators' shares adding up to 100
+/// Stage creators have to keep the PAW first, which the crop slots of the constraint model require
+fn valid_stage_data(data: &StageMetadataData, authority: &Pubkey) -> bool {
+    let valid_creators = match &data.creators {
+        Some(creators) => {
+            creators.first().map(|creator| creator.address) == Some(*authority)
+                && creators.len() <= MAX_CREATOR_LIMIT
+                && creators.iter().map(|creator| creator.share as u16).sum::<u16>() == 100
+        }
+        None => true,
+    };
+
+    valid_creators
+        && data.name.len() <= MAX_NAME_LENGTH
+        && data.symbol.len() <= MAX_SYMBOL_LENGTH
+        && data.uri.len() <= MAX_URI_LENGTH
+        && data.seller_fee_basis_points <= 10000
+}
+
+/// The tomato is built into FarmsPda, any other species has to be registered first
+fn require_species(species_id: u8, species: Option<&Species>) -> Result<()> {
+    if species_id != TOMATO_SPECIES_ID {
+        require!(
+            species.map(|species| species.species_id) == Some(species_id),
+            FarmerHouseError::InvalidSpecies
+        );
+    }
+
+    Ok(())
+}
+
+pub fn set_produce(ctx: Context<SetProduce>, species_id: u8, config: ProduceConfig) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require_species(species_id, ctx.accounts.species.as_deref().map(|species| &**species))?;
+
+    require!(
+        config.bronze_yield > 0
+            && config.silver_yield >= config.bronze_yield
+            && config.gold_yield >= config.silver_yield
+            && config.unit_price > 0,
+        FarmerHouseError::InvalidProduceConfig
+    );
+
+    let produce_info = &mut ctx.accounts.produce;
+    produce_info.bump = *ctx.bumps.get("produce").unwrap();
+    produce_info.species_id = species_id;
+    produce_info.mint = ctx.accounts.produce_mint.key();
+    produce_info.config = config;
+
+    Ok(())
+}
+
+pub fn set_companions(
+    ctx: Context<SetCompanions>,
+    species_id: u8,
+    effects: Vec<CompanionEffect>,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require_species(species_id, ctx.accounts.species.as_deref().map(|species| &**species))?;
+
+    require!(effects.len() <= MAX_COMPANIONS, FarmerHouseError::InvalidCompanions);
+    for (index, effect) in effects.iter().enumerate() {
+        require!(
+            (-10000..=10000).contains(&effect.growth_time_bps)
+                && effects[..index]
+                    .iter()
+                    .all(|other| other.neighbour_species_id != effect.neighbour_species_id),
+            FarmerHouseError::InvalidCompanions
+        );
+    }
+
+    let companions_info = &mut ctx.accounts.companions;
+    companions_info.bump = *ctx.bumps.get("companions").unwrap();
+    companions_info.species_id = species_id;
+    companions_info.effects = effects;
+
+    Ok(())
+}
+
+pub fn set_mutation(
+    ctx: Context<SetMutation>,
+    species_id: u8,
+    config: MutationConfig,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require_species(species_id, ctx.accounts.species.as_deref().map(|species| &**species))?;
+
+    // The cap can be raised or lowered, but never below the crops that already mutated
+    let mutation_info = &mut ctx.accounts.mutation;
+    require!(
+        config.collection != Pubkey::default()
+            && config.chance_bps <= 10000
+            && config.max_supply >= mutation_info.supply
+            && valid_stage_data(&config.data, &ctx.accounts.farms_pda.authority_address),
+        FarmerHouseError::InvalidMutation
+    );
+
+    mutation_info.bump = *ctx.bumps.get("mutation").unwrap();
+    mutation_info.species_id = species_id;
+    mutation_info.config = config;
+
+    Ok(())
+}
+
+pub fn set_events(ctx: Context<SetEvents>, config: EventConfig) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require!(
+        config.reveal_delay > 0 && config.bumper_bps as u64 + config.pest_bps as u64 <= 10000,
+        FarmerHouseError::InvalidEventConfig
+    );
+
+    let events_info = &mut ctx.accounts.events;
+    events_info.bump = *ctx.bumps.get("events").unwrap();
+    events_info.config = config;
+
+    Ok(())
+}
+
+pub fn set_recipe(ctx: Context<SetRecipe>, recipe_id: u8, config: RecipeConfig) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    let output_mint = ctx.accounts.output_mint.key();
+    require!(
+        !config.inputs.is_empty()
+            && config.inputs.len() <= MAX_RECIPE_INPUTS
+            && config.output_amount > 0
+            && config.processing_time >= 0,
+        FarmerHouseError::InvalidRecipe
+    );
+    for (index, input) in config.inputs.iter().enumerate() {
+        // Each mint or collection is burned once per batch, and a recipe can't feed on its own output
+        require!(
+            input.amount > 0
+                && input.mint != output_mint
+                && Some(input.mint) != config.output_collection
+                && config.inputs[..index].iter().all(|other| other.mint != input.mint),
+            FarmerHouseError::InvalidRecipe
+        );
+    }
+
+    let recipe_info = &mut ctx.accounts.recipe;
+    recipe_info.bump = *ctx.bumps.get("recipe").unwrap();
+    recipe_info.recipe_id = recipe_id;
+    recipe_info.output_mint = output_mint;
+    recipe_info.config = config;
+
+    Ok(())
+}
+
+pub fn migrate_farms_pda(ctx: Context<MigrateFarmsPda>) -> Result<()> {
+    let farms_pda_info = ctx.accounts.farms_pda.to_account_info();
+
+    // A v1 farms PDA is shorter than the current layout and can't be deserialized, so it's checked by hand
+    let (version, bump, authority_address, instance_id) = {
+        let data = farms_pda_info.try_borrow_data()?;
+        require!(
+            (data.len() == FarmsPda::V1_SPACE || data.len() >= FarmsPda::SPACE)
+                && data[..8] == FarmsPda::DISCRIMINATOR,
+            FarmerHouseError::InvalidFarmsPda
+        );
+        // v1 accounts have no version byte, nor an instance id, and keep deriving without it once
+        // migrated
+        let (version, instance_id) = if data.len() == FarmsPda::V1_SPACE {
+            (1, None)
+        } else {
+            let offset = FarmsPda::V1_SPACE + 32 + 32 + 32;
+            let legacy_seeds = data[offset + 2 + 1 + 58] != 0;
+            (
+                data[offset + 2],
+                (!legacy_seeds).then(|| u16::from_le_bytes([data[offset], data[offset + 1]])),
+            )
+        };
+        require!(
+            version < FARMS_PDA_VERSION,
+            FarmerHouseError::FarmsPdaUpToDate
+        );
+        (
+            version,
+            data[8],
+            Pubkey::try_from(&data[9..41]).unwrap(),
+            instance_id,
+        )
+    };
+
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    // A v1 farms PDA was the single farm of the program, derived without an instance id
+    let instance_seed = instance_id.map(u16::to_le_bytes);
+    let program_id = ctx.accounts.farmer_house_program.key();
+    let mut seeds: Vec<&[u8]> = vec![b"farmer-house-farms", program_id.as_ref()];
+    if let Some(instance_seed) = &instance_seed {
+        seeds.push(instance_seed);
+    }
+    let bump_seed = [bump];
+    seeds.push(&bump_seed);
+    let farms_pda_address = Pubkey::create_program_address(&seeds, &program_id)
+        .map_err(|_| FarmerHouseError::InvalidFarmsPda)?;
+    require_keys_eq!(
+        farms_pda_address,
+        farms_pda_info.key(),
+        FarmerHouseError::InvalidFarmsPda
+    );
+
+    // The authority pays for the rent of the extra space
+    let rent = Rent::get()?.minimum_balance(FarmsPda::SPACE);
+    if rent > farms_pda_info.lamports() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.pda_authority.to_account_info(),
+                    to: farms_pda_info.clone(),
+                },
+            ),
+            rent - farms_pda_info.lamports(),
+        )?;
+    }
+    farms_pda_info.realloc(FarmsPda::SPACE, true)?;
+
+    let mut farms_pda = FarmsPda::try_deserialize(&mut &farms_pda_info.try_borrow_data()?[..])?;
+    if version < 2 {
+        // Everything v1 was missing reads as zeroes after the realloc
+        farms_pda.growth_config = GrowthConfig::default();
+        farms_pda.legacy_seeds = true;
+    }
+    if version < 3 {
+        farms_pda.prices = ShopPrices::default();
+    }
+    if version < 4 {
+        // A farm that lowered its field price can't start buying fields back for more than it sells them
+        farms_pda.prices.field_buyback = ShopPrices::default()
+            .field_buyback
+            .min(farms_pda.prices.field);
+    }
+    if version < 5 {
+        farms_pda.prices.market_fee_bps = ShopPrices::default().market_fee_bps;
+    }
+    farms_pda.version = FARMS_PDA_VERSION;
+    farms_pda.try_serialize(&mut &mut farms_pda_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+pub fn update_growth_config(
+    ctx: Context<UpdateGrowthConfig>,
+    growth_config: GrowthConfig,
+) -> Result<()> {
+    let farms_pda_info = &mut ctx.accounts.farms_pda;
+
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        farms_pda_info.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require!(
+        growth_config.watering_window > 0
+            && growth_config.wither_deadline >= growth_config.watering_window
+            && growth_config.stage_growth_time >= 0
+            && growth_config.stage_growth_time <= growth_config.wither_deadline
+            && growth_config.fertilizer_boost >= 0,
+        FarmerHouseError::InvalidGrowthConfig
+    );
+
+    farms_pda_info.growth_config = growth_config;
+
+    Ok(())
+}
+
+pub fn update_prices(ctx: Context<UpdatePrices>, prices: ShopPrices) -> Result<()> {
+    let farms_pda_info = &mut ctx.accounts.farms_pda;
+
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        farms_pda_info.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require!(
+        prices.field > 0
+            && prices.seed > 0
+            && prices.fertilizer > 0
+            && prices.field_buyback <= prices.field
+            && prices.market_fee_bps <= 10000,
+        FarmerHouseError::InvalidPrices
+    );
+
+    farms_pda_info.prices = prices;
+
+    Ok(())
+}
+
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        ctx.accounts.farms_pda.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    require!(
+        amount > 0 && ctx.accounts.spl_ata_source.amount >= amount,
+        FarmerHouseError::AmountMismatch
+    );
+
+    // Transfer spl token from ATA of farmsPda to the destination chosen by the authority
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.spl_ata_source.to_account_info(),
+                to: ctx.accounts.spl_ata_destination.to_account_info(),
+                authority: ctx.accounts.farms_pda.to_account_info(),
+            },
+            &[&[
+                b"farmer-house-farms",
+                ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
+                &[ctx.accounts.farms_pda.bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+pub fn init_fertilizer(ctx: Context<InitFertilizer>) -> Result<()> {
+    let farms_pda_info = &mut ctx.accounts.farms_pda;
+
+    require_eq!(
+        ctx.accounts.pda_authority.key(),
+        farms_pda_info.authority_address,
+        FarmerHouseError::AuthorityMismatch
+    );
+
+    farms_pda_info.fertilizer_mint_address = ctx.accounts.fertilizer_mint.key();
+
+    // The shop sells fertilizer out of this account, so the authority has to stock it after initialization
+    if *ctx.accounts.pda_fertilizer_token_account.owner != token::spl_token::ID
+        && ctx.accounts.pda_fertilizer_token_account.lamports() == 0
+    {
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.pda_authority.to_account_info(),
+                associated_token: ctx.accounts.pda_fertilizer_token_account.to_account_info(),
+                authority: ctx.accounts.farms_pda.to_account_info(),
+                mint: ctx.accounts.fertilizer_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    Ok(())
+}
+
 #[derive(Accounts)]
+#[instruction(instance_id: u16)]
 pub struct InitFarmsPda<'info> {
     #[account(mut)]
     pub pda_authority: Signer<'info>,
-    // space:
-    // 8 discriminator + 1 bump + 32 authority_address + 32 spl_mint_address + 32 tomato_seed_collection_address
-    // + 32 young_tomato_seed_collection_address + 32 ripe_tomato_collection_address + 32 field_collection
     #[account(
         init,
         payer = pda_authority,
-        space = 8 + 1 + 32 + 32 + 32 + 32 + 32 + 32,
-        seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()],
+        space = FarmsPda::SPACE,
+        seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &instance_id.to_le_bytes()],
         bump
     )]
     pub farms_pda: Account<'info, FarmsPda>,
@@ -158,7 +667,11 @@ pub struct InitFarmsPda<'info> {
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
     pub token_program: Program<'info, token::Token>,
+    // Only the upgrade authority of the program can initialize the farms, so nobody can front-run the deployment
+    #[account(constraint = farmer_house_program.programdata_address()? == Some(farmer_house_program_data.key()) @ FarmerHouseError::ProgramMismatch)]
     pub farmer_house_program: Program<'info, program_farmer_house>,
+    #[account(constraint = farmer_house_program_data.upgrade_authority_address == Some(pda_authority.key()) @ FarmerHouseError::AuthorityMismatch)]
+    pub farmer_house_program_data: Account<'info, ProgramData>,
 }
 
 #[derive(Accounts)]
@@ -166,18 +679,266 @@ pub struct InitConstraintModel<'info> {
     /// CHECK: Trifle will check this
     #[account(mut)]
     pub escrow_constraint_model: UncheckedAccount<'info>,
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
     pub farms_pda: Account<'info, FarmsPda>,
     #[account(mut)]
     pub pda_authority: Signer<'info>,
+
     /// CHECK: Trifle will check this
-    pub tomato_seed_collection_metadata: UncheckedAccount<'info>,
-    pub tomato_seed_collection_mint: Account<'info, token::Mint>,
+    pub trifle_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub instructions: UncheckedAccount<'info>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(species_id: u8, stage: CropStage)]
+pub struct SetStageMetadata<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = StageMetadata::SPACE,
+        seeds = [b"farmer-house-stage", farms_pda.key().as_ref(), &[species_id], &[stage as u8]],
+        bump
+    )]
+    pub stage_metadata: Account<'info, StageMetadata>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+#[instruction(species_id: u8)]
+pub struct SetProduce<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = Produce::SPACE,
+        seeds = [b"farmer-house-produce", farms_pda.key().as_ref(), &[species_id]],
+        bump
+    )]
+    pub produce: Box<Account<'info, Produce>>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        seeds = [b"farmer-house-produce-mint", farms_pda.key().as_ref(), &[species_id]],
+        bump,
+        mint::decimals = 0,
+        mint::authority = farms_pda
+    )]
+    pub produce_mint: Box<Account<'info, token::Mint>>,
+    // Where the shop keeps the produce it buys back
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        associated_token::mint = produce_mint,
+        associated_token::authority = farms_pda
+    )]
+    pub pda_produce_token_account: Box<Account<'info, token::TokenAccount>>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
 
+#[derive(Accounts)]
+#[instruction(species_id: u8)]
+pub struct SetCompanions<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = Companions::SPACE,
+        seeds = [b"farmer-house-companions", farms_pda.key().as_ref(), &[species_id]],
+        bump
+    )]
+    pub companions: Box<Account<'info, Companions>>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+#[instruction(species_id: u8)]
+pub struct SetMutation<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = Mutation::SPACE,
+        seeds = [b"farmer-house-mutation", farms_pda.key().as_ref(), &[species_id]],
+        bump
+    )]
+    pub mutation: Box<Account<'info, Mutation>>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct SetEvents<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = Events::SPACE,
+        seeds = [b"farmer-house-events", farms_pda.key().as_ref()],
+        bump
+    )]
+    pub events: Box<Account<'info, Events>>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipe_id: u8)]
+pub struct SetRecipe<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        space = Recipe::SPACE,
+        seeds = [b"farmer-house-recipe", farms_pda.key().as_ref(), &[recipe_id]],
+        bump
+    )]
+    pub recipe: Box<Account<'info, Recipe>>,
+    #[account(
+        init_if_needed,
+        payer = pda_authority,
+        seeds = [b"farmer-house-recipe-mint", farms_pda.key().as_ref(), &[recipe_id]],
+        bump,
+        mint::decimals = 0,
+        mint::authority = farms_pda
+    )]
+    pub output_mint: Box<Account<'info, token::Mint>>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateFarmsPda<'info> {
+    /// CHECK: An outdated farms PDA can't be deserialized, migrate_farms_pda checks it by hand
+    #[account(mut, owner = program_farmer_house::id())]
+    pub farms_pda: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGrowthConfig<'info> {
+    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    pub pda_authority: Signer<'info>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePrices<'info> {
+    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    pub pda_authority: Signer<'info>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    pub pda_authority: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = farms_pda,
+    )]
+    pub spl_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut, token::mint = farms_pda.spl_mint_address)]
+    pub spl_ata_destination: Box<Account<'info, token::TokenAccount>>,
+    pub token_program: Program<'info, token::Token>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct InitFertilizer<'info> {
+    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    /// CHECK: associated_token_program will check this
+    #[account(mut)]
+    pub pda_fertilizer_token_account: UncheckedAccount<'info>,
+    pub fertilizer_mint: Account<'info, token::Mint>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct AddToolSlot<'info> {
     /// CHECK: Trifle will check this
+    #[account(mut)]
+    pub escrow_constraint_model: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    /// CHECK: Trifle will check this
+    pub tool_collection_metadata: UncheckedAccount<'info>,
+    pub tool_collection_mint: Account<'info, token::Mint>,
+
+    /// CHECK: Checked against the Trifle program ID
     pub trifle_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub farmer_house_program: Program<'info, program_farmer_house>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub instructions: UncheckedAccount<'info>,
 }
+
+#[derive(Accounts)]
+#[instruction(species_id: u8)]
+pub struct RegisterSpecies<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+    #[account(mut)]
+    pub pda_authority: Signer<'info>,
+    #[account(
+        init,
+        payer = pda_authority,
+        space = Species::SPACE,
+        seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species_id]],
+        bump
+    )]
+    pub species: Account<'info, Species>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -0,0 +1,280 @@
+use {
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, FarmsPda, Lease, LeaseTerms,
+    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{associated_token, token},
+    mpl_token_metadata::{
+        state::{Metadata, TokenMetadataAccount},
+        ID as TOKEN_METADATA_ID,
+    },
+};
+
+/// Offers a field for lease, moving it into the vault of the lease until the lease ends
+pub fn offer_lease(ctx: Context<OfferLease>, terms: LeaseTerms) -> Result<()> {
+    require!(
+        terms.duration > 0 && terms.share_bps <= 10000,
+        FarmerHouseError::InvalidLeaseTerms
+    );
+
+    let metadata_info = Metadata::from_account_info(&ctx.accounts.field_metadata.to_account_info())?;
+    require!(
+        matches!(
+            &metadata_info.collection,
+            Some(collection) if collection.key == ctx.accounts.farms_pda.field_collection_address
+        ),
+        FarmerHouseError::CollectionMismatch
+    );
+    require_keys_eq!(
+        metadata_info.mint,
+        ctx.accounts.field_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+
+    let lease_info = &mut ctx.accounts.lease;
+    lease_info.bump = *ctx.bumps.get("lease").unwrap();
+    lease_info.vault_bump = *ctx.bumps.get("lease_vault").unwrap();
+    lease_info.field_mint = ctx.accounts.field_mint.key();
+    lease_info.owner = ctx.accounts.user_account.key();
+    lease_info.tenant = Pubkey::default();
+    lease_info.expires_at = 0;
+    lease_info.terms = terms;
+
+    // Transfer fieldNFT from ATA of user to ATA of the lease vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+/// Takes an offered lease, paying its fee to the owner of the field
+pub fn take_lease(ctx: Context<TakeLease>) -> Result<()> {
+    let lease_info = &mut ctx.accounts.lease;
+    require_keys_eq!(
+        lease_info.tenant,
+        Pubkey::default(),
+        FarmerHouseError::LeaseTaken
+    );
+    require_keys_eq!(
+        ctx.accounts.spl_ata_destination.owner,
+        lease_info.owner,
+        FarmerHouseError::OwnerMismatch
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.spl_ata_source.to_account_info(),
+                to: ctx.accounts.spl_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        lease_info.terms.fee,
+    )?;
+
+    lease_info.tenant = ctx.accounts.user_account.key();
+    lease_info.expires_at = Clock::get()?
+        .unix_timestamp
+        .saturating_add(lease_info.terms.duration);
+    msg!("Field leased until {}", lease_info.expires_at);
+
+    Ok(())
+}
+
+/// Returns the field to its owner once the lease expired, or before anyone took it. Crops still in the
+/// field stay with it.
+pub fn end_lease(ctx: Context<EndLease>) -> Result<()> {
+    let lease_info = &ctx.accounts.lease;
+    require_keys_eq!(
+        lease_info.owner,
+        ctx.accounts.user_account.key(),
+        FarmerHouseError::OwnerMismatch
+    );
+    let taken = lease_info.tenant != Pubkey::default();
+    require!(
+        !taken || Clock::get()?.unix_timestamp >= lease_info.expires_at,
+        FarmerHouseError::LeaseActive
+    );
+
+    let lease = lease_info.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"farmer-house-lease-vault",
+        lease.as_ref(),
+        &[lease_info.vault_bump],
+    ];
+
+    // Transfer fieldNFT from ATA of the lease vault back to ATA of the owner
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
+                authority: ctx.accounts.lease_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.field_ata_source.to_account_info(),
+            destination: ctx.accounts.user_account.to_account_info(),
+            authority: ctx.accounts.lease_vault.to_account_info(),
+        },
+        &[vault_seeds],
+    ))?;
+
+    // What is left of the float the tenant topped the vault up with goes back to them
+    let float = ctx.accounts.lease_vault.lamports();
+    if taken && float > 0 {
+        let tenant_account = ctx
+            .accounts
+            .tenant_account
+            .as_ref()
+            .ok_or(FarmerHouseError::LeaseMismatch)?;
+        require_keys_eq!(
+            tenant_account.key(),
+            lease_info.tenant,
+            FarmerHouseError::LeaseMismatch
+        );
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.lease_vault.to_account_info(),
+                    to: tenant_account.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            float,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OfferLease<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: offer_lease checks this is the metadata of the field
+    #[account(owner = TOKEN_METADATA_ID)]
+    pub field_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init,
+        payer = user_account,
+        space = Lease::SPACE,
+        seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+    #[account(seeds = [b"farmer-house-lease-vault", lease.key().as_ref()], bump)]
+    pub lease_vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = lease_vault
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct TakeLease<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), lease.field_mint.as_ref()],
+        bump = lease.bump
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = user_account,
+    )]
+    pub spl_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut, token::mint = farms_pda.spl_mint_address)]
+    pub spl_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct EndLease<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-lease", farms_pda.key().as_ref(), field_mint.key().as_ref()],
+        bump = lease.bump
+    )]
+    pub lease: Box<Account<'info, Lease>>,
+    #[account(mut, seeds = [b"farmer-house-lease-vault", lease.key().as_ref()], bump = lease.vault_bump)]
+    pub lease_vault: SystemAccount<'info>,
+    /// CHECK: end_lease checks this is the tenant, only needed once the lease was taken
+    #[account(mut)]
+    pub tenant_account: Option<UncheckedAccount<'info>>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = lease_vault
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -1,14 +1,64 @@
 use anchor_lang::prelude::*;
 
+pub mod coop;
+pub mod craft;
 pub mod errors;
 pub mod farm;
+pub mod farmhand;
 pub mod init;
+pub mod lease;
+pub mod market;
 pub mod shop;
+pub mod vault;
+#[cfg(feature = "view")]
+pub mod view;
 
+use coop::*;
+use craft::*;
 use farm::*;
+use farmhand::*;
 use init::*;
+use lease::*;
+use market::*;
 use shop::*;
 
+/// Trifle slots crops can be planted in
+pub const CROP_SLOTS: [&str; 6] = ["a1", "a2", "a3", "b1", "b2", "b3"];
+
+/// How the crop slots are laid out in a field, one row per letter and one column per digit
+pub const CROP_GRID: [[&str; 3]; 2] = [["a1", "a2", "a3"], ["b1", "b2", "b3"]];
+
+/// Trifle slot reserved for the equipped tool of a field
+pub const TOOL_SLOT: &str = "tool";
+
+/// Species ID of the tomato, whose collections are stored in `FarmsPda`
+pub const TOMATO_SPECIES_ID: u8 = 0;
+
+/// Layout version of FarmsPda, bumped whenever fields are carved out of its reserved padding
+pub const FARMS_PDA_VERSION: u8 = 6;
+
+/// Fertility of untouched soil, and the most rotating crops can restore it to
+pub const MAX_FERTILITY: u8 = 100;
+
+/// Fertility each harvest takes from the soil of its slot
+pub const HARVEST_DEPLETION: u8 = 25;
+
+/// Fertility restored by planting a species other than the one last planted in the slot
+pub const ROTATION_RECOVERY: u8 = 50;
+
+/// Most neighbouring species a species can have companion effects with
+pub const MAX_COMPANIONS: usize = 8;
+
+/// Most distinct tokens a recipe can take as inputs
+pub const MAX_RECIPE_INPUTS: usize = 4;
+
+/// Most members a co-op can have
+pub const MAX_COOP_MEMBERS: usize = 8;
+
+/// Lamports the tenant, farmhand or co-op member tops the vault holding a field up to, to pay the
+/// Trifle fees and rent of the transfers it signs as the holder of the field
+pub const LEASE_VAULT_FLOAT: u64 = 10_000_000;
+
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
 #[program]
@@ -17,6 +67,7 @@ pub mod farmer_house {
 
     pub fn initialize_farms_pda(
         ctx: Context<InitFarmsPda>,
+        instance_id: u16,
         tomato_seed_collection_address: Pubkey,
         tomato_sappling_collection_address: Pubkey,
         ripe_tomato_collection_address: Pubkey,
@@ -24,6 +75,7 @@ pub mod farmer_house {
     ) -> Result<()> {
         init_farms_pda(
             ctx,
+            instance_id,
             tomato_seed_collection_address,
             tomato_sappling_collection_address,
             ripe_tomato_collection_address,
@@ -39,6 +91,90 @@ pub mod farmer_house {
         init_constraint_model(ctx, name, schema_uri)
     }
 
+    pub fn migrate_farms_pda(ctx: Context<MigrateFarmsPda>) -> Result<()> {
+        init::migrate_farms_pda(ctx)
+    }
+
+    pub fn update_prices(ctx: Context<UpdatePrices>, prices: ShopPrices) -> Result<()> {
+        init::update_prices(ctx, prices)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        init::withdraw_treasury(ctx, amount)
+    }
+
+    pub fn update_growth_config(
+        ctx: Context<UpdateGrowthConfig>,
+        growth_config: GrowthConfig,
+    ) -> Result<()> {
+        init::update_growth_config(ctx, growth_config)
+    }
+
+    pub fn initialize_fertilizer(ctx: Context<InitFertilizer>) -> Result<()> {
+        init_fertilizer(ctx)
+    }
+
+    pub fn add_tool_slot(ctx: Context<AddToolSlot>) -> Result<()> {
+        init::add_tool_slot(ctx)
+    }
+
+    pub fn register_species(
+        ctx: Context<RegisterSpecies>,
+        species_id: u8,
+        seed_collection_address: Pubkey,
+        sapling_collection_address: Pubkey,
+        ripe_collection_address: Pubkey,
+    ) -> Result<()> {
+        init::register_species(
+            ctx,
+            species_id,
+            seed_collection_address,
+            sapling_collection_address,
+            ripe_collection_address,
+        )
+    }
+
+    pub fn set_stage_metadata(
+        ctx: Context<SetStageMetadata>,
+        species_id: u8,
+        stage: CropStage,
+        data: StageMetadataData,
+    ) -> Result<()> {
+        init::set_stage_metadata(ctx, species_id, stage, data)
+    }
+
+    pub fn set_produce(
+        ctx: Context<SetProduce>,
+        species_id: u8,
+        config: ProduceConfig,
+    ) -> Result<()> {
+        init::set_produce(ctx, species_id, config)
+    }
+
+    pub fn set_companions(
+        ctx: Context<SetCompanions>,
+        species_id: u8,
+        effects: Vec<CompanionEffect>,
+    ) -> Result<()> {
+        init::set_companions(ctx, species_id, effects)
+    }
+
+    pub fn set_events(ctx: Context<SetEvents>, config: EventConfig) -> Result<()> {
+        init::set_events(ctx, config)
+    }
+
+    pub fn set_mutation(
+        ctx: Context<SetMutation>,
+        species_id: u8,
+        config: MutationConfig,
+    ) -> Result<()> {
+        init::set_mutation(ctx, species_id, config)
+    }
+
+    pub fn set_recipe(ctx: Context<SetRecipe>, recipe_id: u8, config: RecipeConfig) -> Result<()> {
+        init::set_recipe(ctx, recipe_id, config)
+    }
+
     pub fn plant_seed(ctx: Context<PlantSeed>, crop_slot: String) -> Result<()> {
         farm::plant_seed(ctx, crop_slot)
     }
@@ -47,10 +183,30 @@ pub mod farmer_house {
         shop::buy_field(ctx)
     }
 
+    pub fn sell_field(ctx: Context<SellField>) -> Result<()> {
+        shop::sell_field(ctx)
+    }
+
     pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
         shop::buy_seed(ctx)
     }
 
+    pub fn equip_tool(ctx: Context<EquipTool>) -> Result<()> {
+        farm::equip_tool(ctx)
+    }
+
+    pub fn unequip_tool(ctx: Context<UnequipTool>) -> Result<()> {
+        farm::unequip_tool(ctx)
+    }
+
+    pub fn buy_fertilizer(ctx: Context<BuyFertilizer>, amount: u64) -> Result<()> {
+        shop::buy_fertilizer(ctx, amount)
+    }
+
+    pub fn fertilize(ctx: Context<Fertilize>, crop_slot: String) -> Result<()> {
+        farm::fertilize(ctx, crop_slot)
+    }
+
     pub fn water(ctx: Context<Water>, crop_slot: String) -> Result<()> {
         farm::water(ctx, crop_slot)
     }
@@ -59,12 +215,79 @@ pub mod farmer_house {
         farm::harvest_crop(ctx, crop_slot)
     }
 
-    pub fn sell_crop(ctx: Context<SellCrop>) -> Result<()> {
-        shop::sell_crop(ctx)
+    pub fn reveal_crop(ctx: Context<RevealCrop>) -> Result<()> {
+        farm::reveal_crop(ctx)
+    }
+
+    pub fn clear_slot(ctx: Context<ClearSlot>, crop_slot: String) -> Result<()> {
+        farm::clear_slot(ctx, crop_slot)
+    }
+
+    pub fn sell_crop<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellCrop<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        shop::sell_crop(ctx, amount)
+    }
+
+    pub fn craft<'info>(ctx: Context<'_, '_, '_, 'info, Craft<'info>>, batches: u64) -> Result<()> {
+        craft::craft(ctx, batches)
+    }
+
+    pub fn collect_craft<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectCraft<'info>>,
+    ) -> Result<()> {
+        craft::collect_craft(ctx)
+    }
+
+    pub fn offer_lease(ctx: Context<OfferLease>, terms: LeaseTerms) -> Result<()> {
+        lease::offer_lease(ctx, terms)
+    }
+
+    pub fn take_lease(ctx: Context<TakeLease>) -> Result<()> {
+        lease::take_lease(ctx)
+    }
+
+    pub fn end_lease(ctx: Context<EndLease>) -> Result<()> {
+        lease::end_lease(ctx)
+    }
+
+    pub fn delegate_farmhand(
+        ctx: Context<DelegateFarmhand>,
+        delegate: Pubkey,
+        actions: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        farmhand::delegate_farmhand(ctx, delegate, actions, expires_at)
+    }
+
+    pub fn revoke_farmhand(ctx: Context<RevokeFarmhand>) -> Result<()> {
+        farmhand::revoke_farmhand(ctx)
+    }
+
+    pub fn found_coop(ctx: Context<FoundCoop>, members: Vec<CoopMember>) -> Result<()> {
+        coop::found_coop(ctx, members)
+    }
+
+    pub fn dissolve_coop<'info>(ctx: Context<'_, '_, '_, 'info, DissolveCoop<'info>>) -> Result<()> {
+        coop::dissolve_coop(ctx)
+    }
+
+    pub fn create_listing(ctx: Context<CreateListing>, price: u64) -> Result<()> {
+        market::create_listing(ctx, price)
+    }
+
+    pub fn buy_listing(ctx: Context<BuyListing>, price: u64) -> Result<()> {
+        market::buy_listing(ctx, price)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        market::cancel_listing(ctx)
     }
 }
 
 #[account]
+#[derive(Debug)]
 pub struct FarmsPda {
     bump: u8,
     authority_address: Pubkey,
@@ -73,4 +296,909 @@ pub struct FarmsPda {
     tomato_sappling_collection_address: Pubkey,
     ripe_tomato_collection_address: Pubkey,
     field_collection_address: Pubkey,
+    growth_config: GrowthConfig,
+    fertilizer_mint_address: Pubkey,
+    tool_collection_address: Pubkey,
+    instance_id: u16,
+    version: u8,
+    prices: ShopPrices,
+    /// Set on farms migrated from v1, which are still derived without an instance id
+    legacy_seeds: bool,
+    reserved: [u8; 5],
+}
+
+impl FarmsPda {
+    // 8 discriminator + 1 bump + 32 authority_address + 32 spl_mint_address + 32 tomato_seed_collection_address
+    // + 32 young_tomato_seed_collection_address + 32 ripe_tomato_collection_address + 32 field_collection
+    pub const V1_SPACE: usize = 8 + 1 + 32 + 32 + 32 + 32 + 32 + 32;
+    // v1 + 32 growth_config + 32 fertilizer_mint_address + 32 tool_collection_address + 2 instance_id
+    // + 1 version + 58 prices + 1 legacy_seeds + 5 reserved
+    pub const SPACE: usize = Self::V1_SPACE + 32 + 32 + 32 + 2 + 1 + 58 + 1 + 5;
+
+    /// The seed of the instance id the farm is derived from, empty for farms migrated from v1, whose
+    /// address only derives from `[b"farmer-house-farms", program_id]`
+    pub fn instance_seed(&self) -> Vec<u8> {
+        if self.legacy_seeds {
+            vec![]
+        } else {
+            self.instance_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// The collections of the species passed to an instruction, or of the tomato when none is passed
+    pub fn species_collections(&self, species: Option<&Species>) -> SpeciesCollections {
+        match species {
+            Some(species) => SpeciesCollections {
+                species_id: species.species_id,
+                seed: species.seed_collection_address,
+                sapling: species.sapling_collection_address,
+                ripe: species.ripe_collection_address,
+            },
+            None => SpeciesCollections {
+                species_id: TOMATO_SPECIES_ID,
+                seed: self.tomato_seed_collection_address,
+                sapling: self.tomato_sappling_collection_address,
+                ripe: self.ripe_tomato_collection_address,
+            },
+        }
+    }
+
+    /// What the shop pays for a crop NFT in `collection`: the buyback of its stage, or the sell price of
+    /// the mutation its `CropState` flags it with, times the grade it was harvested with
+    pub fn crop_price(
+        &self,
+        species: Option<&Species>,
+        collection: &Pubkey,
+        crop_state: Option<&CropState>,
+        mutation: Option<&Mutation>,
+    ) -> Result<u64> {
+        let stage = self.species_collections(species).stage_of(collection);
+        let mut price = self.prices.buyback(stage.unwrap_or(CropStage::Seed));
+
+        // Any NFT can name the collection of a mutation, only the CropState tells a mutated crop
+        if let (Some(crop_state), Some(mutation)) = (crop_state, mutation) {
+            if crop_state.mutated
+                && mutation.species_id == crop_state.species_id
+                && mutation.config.collection == *collection
+            {
+                price = mutation.config.sell_price;
+            }
+        }
+
+        // Harvested crops are worth more the better they were cared for
+        match crop_state.and_then(|crop_state| crop_state.grade) {
+            Some(grade) => grade.price_of(price),
+            None => Ok(price),
+        }
+    }
+}
+
+#[account]
+#[derive(Debug)]
+pub struct Species {
+    bump: u8,
+    species_id: u8,
+    seed_collection_address: Pubkey,
+    sapling_collection_address: Pubkey,
+    ripe_collection_address: Pubkey,
+}
+
+impl Species {
+    // 8 discriminator + 1 bump + 1 species_id + 32 seed_collection_address
+    // + 32 sapling_collection_address + 32 ripe_collection_address
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + 32 + 32;
+}
+
+/// The collections a crop moves through as it grows, one per stage
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpeciesCollections {
+    pub species_id: u8,
+    pub seed: Pubkey,
+    pub sapling: Pubkey,
+    pub ripe: Pubkey,
+}
+
+impl SpeciesCollections {
+    pub fn stage_of(&self, collection: &Pubkey) -> Option<CropStage> {
+        if *collection == self.seed {
+            Some(CropStage::Seed)
+        } else if *collection == self.sapling {
+            Some(CropStage::Sapling)
+        } else if *collection == self.ripe {
+            Some(CropStage::Ripe)
+        } else {
+            None
+        }
+    }
+
+    pub fn collection_of(&self, stage: CropStage) -> Pubkey {
+        match stage {
+            CropStage::Seed => self.seed,
+            CropStage::Sapling => self.sapling,
+            CropStage::Ripe => self.ripe,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CropStage {
+    Seed,
+    Sapling,
+    Ripe,
+}
+
+impl CropStage {
+    /// The stage watering advances the crop to, none once it is ripe
+    pub fn next(&self) -> Option<CropStage> {
+        match self {
+            CropStage::Seed => Some(CropStage::Sapling),
+            CropStage::Sapling => Some(CropStage::Ripe),
+            CropStage::Ripe => None,
+        }
+    }
+}
+
+#[account]
+#[derive(Debug)]
+pub struct StageMetadata {
+    bump: u8,
+    species_id: u8,
+    stage: CropStage,
+    data: StageMetadataData,
+}
+
+impl StageMetadata {
+    // 8 discriminator + 1 bump + 1 species_id + 1 stage + 4+32 name + 4+10 symbol + 4+200 uri
+    // + 2 seller_fee_basis_points + 1+4+5*33 creators
+    pub const SPACE: usize = 8 + 1 + 1 + 1 + 36 + 14 + 204 + 2 + 170;
+}
+
+/// Metadata a crop takes on when watering advances it to a stage
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StageMetadataData {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    /// Replaces the creators of the crop, which are kept as they are when none
+    pub creators: Option<Vec<StageCreator>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StageCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// The fungible produce a species yields when harvested, in place of the ripe crop itself
+#[account]
+#[derive(Debug)]
+pub struct Produce {
+    bump: u8,
+    species_id: u8,
+    mint: Pubkey,
+    config: ProduceConfig,
+}
+
+impl Produce {
+    // 8 discriminator + 1 bump + 1 species_id + 32 mint + 32 config
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + 32;
+}
+
+/// How much produce a harvest yields for each grade, and what the shop pays for it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProduceConfig {
+    pub bronze_yield: u64,
+    pub silver_yield: u64,
+    pub gold_yield: u64,
+    /// Price of one unit of produce, in base units of the currency token
+    pub unit_price: u64,
+}
+
+impl ProduceConfig {
+    pub fn yield_of(&self, grade: CropGrade) -> u64 {
+        match grade {
+            CropGrade::Bronze => self.bronze_yield,
+            CropGrade::Silver => self.silver_yield,
+            CropGrade::Gold => self.gold_yield,
+        }
+    }
+}
+
+/// The crop slots sharing an edge with `crop_slot` in `CROP_GRID`
+pub fn neighbouring_slots(crop_slot: &str) -> Vec<&'static str> {
+    let mut neighbours = vec![];
+    for (row, slots) in CROP_GRID.iter().enumerate() {
+        if let Some(column) = slots.iter().position(|slot| *slot == crop_slot) {
+            if column > 0 {
+                neighbours.push(slots[column - 1]);
+            }
+            if column + 1 < slots.len() {
+                neighbours.push(slots[column + 1]);
+            }
+            if row > 0 {
+                neighbours.push(CROP_GRID[row - 1][column]);
+            }
+            if row + 1 < CROP_GRID.len() {
+                neighbours.push(CROP_GRID[row + 1][column]);
+            }
+        }
+    }
+    neighbours
+}
+
+/// Loads a config PDA the PAW may or may not have set, passed at its derived address either way so
+/// it can't be left out once it's set
+pub fn initialized<'info, T: AccountSerialize + AccountDeserialize + Owner + Clone>(
+    account: &UncheckedAccount<'info>,
+) -> Result<Option<Account<'info, T>>> {
+    if account.data_is_empty() {
+        Ok(None)
+    } else {
+        Account::try_from(account).map(Some)
+    }
+}
+
+/// Companion planting effects a species gets from the crops growing next to it
+#[account]
+#[derive(Debug)]
+pub struct Companions {
+    bump: u8,
+    species_id: u8,
+    effects: Vec<CompanionEffect>,
+}
+
+impl Companions {
+    // 8 discriminator + 1 bump + 1 species_id + 4+8*4 effects
+    pub const SPACE: usize = 8 + 1 + 1 + 4 + MAX_COMPANIONS * CompanionEffect::SPACE;
+
+    /// The effects of the species planted next to the crop, once for each neighbour
+    fn effects_of<'a>(&'a self, neighbours: &'a [u8]) -> impl Iterator<Item = &'a CompanionEffect> {
+        neighbours.iter().filter_map(move |neighbour| {
+            self.effects
+                .iter()
+                .find(|effect| effect.neighbour_species_id == *neighbour)
+        })
+    }
+
+    /// Growth time to the next stage, which can't go below nothing
+    pub fn growth_time(&self, stage_growth_time: i64, neighbours: &[u8]) -> i64 {
+        let bps = self
+            .effects_of(neighbours)
+            .fold(10000i64, |bps, effect| bps + effect.growth_time_bps as i64);
+        stage_growth_time.saturating_mul(bps.max(0)) / 10000
+    }
+
+    /// Produce yield of the harvest, a crop always yields at least one unit
+    pub fn yield_of(&self, base_yield: u64, neighbours: &[u8]) -> u64 {
+        let bonus = self
+            .effects_of(neighbours)
+            .fold(0i64, |bonus, effect| bonus + effect.yield_bonus as i64);
+        let base_yield = i64::try_from(base_yield).unwrap_or(i64::MAX);
+        base_yield.saturating_add(bonus).max(1) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompanionEffect {
+    pub neighbour_species_id: u8,
+    /// Change of the growth time in basis points, negative to grow faster
+    pub growth_time_bps: i16,
+    /// Units of produce added to the harvest yield, negative for a penalty
+    pub yield_bonus: i8,
+}
+
+impl CompanionEffect {
+    // 1 neighbour_species_id + 2 growth_time_bps + 1 yield_bonus
+    pub const SPACE: usize = 1 + 2 + 1;
+}
+
+/// Chances of the events a harvest can bring, drawn from the hash of a slot the crop committed to when
+/// it was planted
+#[account]
+#[derive(Debug)]
+pub struct Events {
+    bump: u8,
+    config: EventConfig,
+}
+
+impl Events {
+    // 8 discriminator + 1 bump + 12 config
+    pub const SPACE: usize = 8 + 1 + 12;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventConfig {
+    /// Slots between planting and the slot whose hash decides the event of the harvest
+    pub reveal_delay: u64,
+    /// Chance of a bumper harvest in basis points
+    pub bumper_bps: u16,
+    /// Chance of pests in basis points
+    pub pest_bps: u16,
+}
+
+impl EventConfig {
+    /// The event a roll between 0 and 9999 brings, pests taking the lowest rolls
+    pub fn event_of(&self, roll: u64) -> Option<CropEvent> {
+        if roll < self.pest_bps as u64 {
+            Some(CropEvent::Pests)
+        } else if roll < self.pest_bps as u64 + self.bumper_bps as u64 {
+            Some(CropEvent::BumperHarvest)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CropEvent {
+    BumperHarvest,
+    Pests,
+}
+
+impl CropEvent {
+    /// A bumper harvest upgrades the grade of the crop, pests leave it Bronze
+    pub fn apply(&self, grade: CropGrade) -> CropGrade {
+        match self {
+            CropEvent::BumperHarvest => grade.upgraded(),
+            CropEvent::Pests => CropGrade::Bronze,
+        }
+    }
+}
+
+/// A rare variety a species can mutate into when watering ripens it, with its own collection and price
+#[account]
+#[derive(Debug)]
+pub struct Mutation {
+    bump: u8,
+    species_id: u8,
+    /// Crops mutated so far, never more than the `max_supply` of the config
+    supply: u32,
+    config: MutationConfig,
+}
+
+impl Mutation {
+    // 8 discriminator + 1 bump + 1 species_id + 4 supply + 32 collection + 2 chance_bps + 8 sell_price
+    // + 4 max_supply + 426 data
+    pub const SPACE: usize = 8 + 1 + 1 + 4 + 32 + 2 + 8 + 4 + 426;
+
+    /// Whether a mutated crop is still within the supply cap
+    pub fn available(&self) -> bool {
+        self.supply < self.config.max_supply
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MutationConfig {
+    /// Collection of the mutated crops, in place of the ripe collection of the species
+    pub collection: Pubkey,
+    /// Chance of a ripening crop to mutate in basis points
+    pub chance_bps: u16,
+    /// What the shop pays for a mutated crop before its grade, in base units of the currency token
+    pub sell_price: u64,
+    pub max_supply: u32,
+    /// Metadata a mutated crop takes on, in place of the ripe stage metadata
+    pub data: StageMetadataData,
+}
+
+/// Turns produce and other fungible tokens into goods, minted by the farms PDA
+#[account]
+#[derive(Debug)]
+pub struct Recipe {
+    bump: u8,
+    recipe_id: u8,
+    output_mint: Pubkey,
+    config: RecipeConfig,
+}
+
+impl Recipe {
+    // 8 discriminator + 1 bump + 1 recipe_id + 32 output_mint + config
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + RecipeConfig::SPACE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecipeConfig {
+    /// What one batch burns, at most `MAX_RECIPE_INPUTS` distinct mints or collections
+    pub inputs: Vec<RecipeInput>,
+    /// Units of the output one batch mints
+    pub output_amount: u64,
+    /// Seconds a batch takes before its output can be collected, `0` to mint it right away
+    pub processing_time: i64,
+    /// Hands out NFTs of this collection stocked in the shop instead of minting the output mint
+    pub output_collection: Option<Pubkey>,
+}
+
+impl RecipeConfig {
+    // 4+4*41 inputs + 8 output_amount + 8 processing_time + 1+32 output_collection
+    pub const SPACE: usize = 4 + MAX_RECIPE_INPUTS * RecipeInput::SPACE + 8 + 8 + 33;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecipeInput {
+    /// The mint burned, or the collection of the NFTs burned with `collection`
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Burns `amount` NFTs of the farm in the collection `mint`, like ripe tomatoes, instead of
+    /// fungible tokens
+    pub collection: bool,
+}
+
+impl RecipeInput {
+    // 32 mint + 8 amount + 1 collection
+    pub const SPACE: usize = 32 + 8 + 1;
+}
+
+/// The output of a craft waiting out the processing time of its recipe
+#[account]
+#[derive(Debug)]
+pub struct CraftState {
+    bump: u8,
+    recipe: Pubkey,
+    amount: u64,
+    ready_at: i64,
+}
+
+impl CraftState {
+    // 8 discriminator + 1 bump + 32 recipe + 8 amount + 8 ready_at
+    pub const SPACE: usize = 8 + 1 + 32 + 8 + 8;
+}
+
+/// A field held in the vault of the lease, which its tenant can plant, water and harvest until the
+/// lease expires
+#[account]
+#[derive(Debug)]
+pub struct Lease {
+    bump: u8,
+    vault_bump: u8,
+    field_mint: Pubkey,
+    owner: Pubkey,
+    /// Left default until a tenant takes the lease
+    tenant: Pubkey,
+    expires_at: i64,
+    terms: LeaseTerms,
+}
+
+impl Lease {
+    // 8 discriminator + 1 bump + 1 vault_bump + 32 field_mint + 32 owner + 32 tenant + 8 expires_at
+    // + 8 fee + 8 duration + 2 share_bps
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 2;
+
+    /// Whether `tenant` took the lease and it has not expired yet
+    pub fn is_held_by(&self, tenant: &Pubkey, now: i64) -> bool {
+        self.tenant == *tenant && now < self.expires_at
+    }
+
+    /// The owner's share of the proceeds of a harvest
+    pub fn share_of(&self, proceeds: u64) -> u64 {
+        (proceeds as u128 * self.terms.share_bps as u128 / 10000) as u64
+    }
+}
+
+impl FieldVault for Lease {
+    const VAULT_SEED: &'static [u8] = b"farmer-house-lease-vault";
+
+    fn vault_bump(&self) -> u8 {
+        self.vault_bump
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeaseTerms {
+    /// Paid by the tenant to the owner when taking the lease, in base units of the currency token
+    pub fee: u64,
+    /// Seconds the lease runs from when it is taken
+    pub duration: i64,
+    /// Share of the proceeds of every harvest paid to the owner, in basis points
+    pub share_bps: u16,
+}
+
+/// A field held in the vault of the farmhand, which its owner and delegate can act on, the delegate
+/// only through the actions allowed until it expires
+#[account]
+#[derive(Debug)]
+pub struct Farmhand {
+    bump: u8,
+    vault_bump: u8,
+    field_mint: Pubkey,
+    owner: Pubkey,
+    delegate: Pubkey,
+    actions: u8,
+    expires_at: i64,
+}
+
+impl Farmhand {
+    // 8 discriminator + 1 bump + 1 vault_bump + 32 field_mint + 32 owner + 32 delegate + 1 actions
+    // + 8 expires_at
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + 32 + 32 + 1 + 8;
+
+    /// Bits of the actions a farmhand can be allowed
+    pub const WATER: u8 = 1;
+    pub const PLANT: u8 = 1 << 1;
+    pub const HARVEST: u8 = 1 << 2;
+    pub const ALL_ACTIONS: u8 = Self::WATER | Self::PLANT | Self::HARVEST;
+
+    /// Whether `user` may take `action` on the field at `now`
+    pub fn allows(&self, user: &Pubkey, action: u8, now: i64) -> bool {
+        *user == self.owner
+            || (*user == self.delegate && self.actions & action == action && now < self.expires_at)
+    }
+}
+
+impl FieldVault for Farmhand {
+    const VAULT_SEED: &'static [u8] = b"farmer-house-farmhand-vault";
+
+    fn vault_bump(&self) -> u8 {
+        self.vault_bump
+    }
+}
+
+/// A field held in the vault of the co-op, which all of its members can plant, water and harvest. Its
+/// harvests stay in the vault until sold, splitting the proceeds between the members by their shares.
+#[account]
+#[derive(Debug)]
+pub struct Coop {
+    bump: u8,
+    vault_bump: u8,
+    field_mint: Pubkey,
+    /// Crops harvested into the vault and not sold yet
+    crops: u32,
+    members: Vec<CoopMember>,
+}
+
+impl Coop {
+    // 8 discriminator + 1 bump + 1 vault_bump + 32 field_mint + 4 crops + 4 members length + the
+    // members
+    pub const SPACE: usize = 8 + 1 + 1 + 32 + 4 + 4 + MAX_COOP_MEMBERS * CoopMember::SPACE;
+
+    pub fn member(&self, wallet: &Pubkey) -> Option<&CoopMember> {
+        self.members.iter().find(|member| member.wallet == *wallet)
+    }
+
+    pub fn total_shares(&self) -> u64 {
+        self.members.iter().map(|member| member.shares as u64).sum()
+    }
+
+    /// Each member's part of `proceeds` by their shares, in the order of the members. What rounding
+    /// leaves over goes to the first member.
+    pub fn split(&self, proceeds: u64) -> Vec<u64> {
+        let total_shares = self.total_shares() as u128;
+        let mut parts: Vec<u64> = self
+            .members
+            .iter()
+            .map(|member| (proceeds as u128 * member.shares as u128 / total_shares) as u64)
+            .collect();
+        parts[0] += proceeds - parts.iter().sum::<u64>();
+        parts
+    }
+}
+
+impl FieldVault for Coop {
+    const VAULT_SEED: &'static [u8] = b"farmer-house-coop-vault";
+
+    fn vault_bump(&self) -> u8 {
+        self.vault_bump
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoopMember {
+    pub wallet: Pubkey,
+    /// The member's weight in splitting the proceeds and dissolving the co-op
+    pub shares: u32,
+}
+
+impl CoopMember {
+    pub const SPACE: usize = 32 + 4;
+}
+
+/// A crop or field escrowed by the listing until another player buys it for `price`, or the seller
+/// cancels it
+#[account]
+#[derive(Debug)]
+pub struct Listing {
+    bump: u8,
+    seller: Pubkey,
+    mint: Pubkey,
+    price: u64,
+}
+
+impl Listing {
+    // 8 discriminator + 1 bump + 32 seller + 32 mint + 8 price
+    pub const SPACE: usize = 8 + 1 + 32 + 32 + 8;
+}
+
+/// An account holding a field in its vault, a system account that signs the Trifle transfers of the
+/// players it lets act on the field in place of its owner
+pub trait FieldVault {
+    /// Seed of the vault, followed by the address of the account
+    const VAULT_SEED: &'static [u8];
+
+    fn vault_bump(&self) -> u8;
+
+    /// Address of the vault, for the account at `key`
+    fn vault(&self, key: &Pubkey) -> Pubkey {
+        Pubkey::create_program_address(&[Self::VAULT_SEED, key.as_ref(), &[self.vault_bump()]], &ID)
+            .unwrap()
+    }
+}
+
+/// Prices of the shop, in base units of the currency token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShopPrices {
+    pub field: u64,
+    pub seed: u64,
+    /// Price of one unit of fertilizer
+    pub fertilizer: u64,
+    /// What the shop pays for a crop at each stage, before its grade is applied
+    pub seed_buyback: u64,
+    pub sapling_buyback: u64,
+    pub ripe_buyback: u64,
+    /// What the shop pays for an empty field sold back to it
+    pub field_buyback: u64,
+    /// Share of the price of a market sale the shop takes as a fee, in basis points
+    pub market_fee_bps: u16,
+}
+
+impl Default for ShopPrices {
+    fn default() -> Self {
+        Self {
+            field: 70000000,
+            seed: 2000000,
+            fertilizer: 1000000,
+            seed_buyback: 2000000,
+            sapling_buyback: 3500000,
+            ripe_buyback: 7000000,
+            field_buyback: 35000000,
+            market_fee_bps: 250,
+        }
+    }
+}
+
+impl ShopPrices {
+    pub fn buyback(&self, stage: CropStage) -> u64 {
+        match stage {
+            CropStage::Seed => self.seed_buyback,
+            CropStage::Sapling => self.sapling_buyback,
+            CropStage::Ripe => self.ripe_buyback,
+        }
+    }
+
+    /// The shop's fee on a market sale for `price`
+    pub fn market_fee(&self, price: u64) -> u64 {
+        (price as u128 * self.market_fee_bps as u128 / 10000) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrowthConfig {
+    /// Seconds after planting or the previous watering during which a watering counts as on time
+    pub watering_window: i64,
+    /// Seconds after planting or the previous watering after which an unripe crop withers
+    pub wither_deadline: i64,
+    /// Seconds a crop has to grow before watering can advance it to the next stage
+    pub stage_growth_time: i64,
+    /// Seconds of growth time removed by each unit of fertilizer
+    pub fertilizer_boost: i64,
+}
+
+impl Default for GrowthConfig {
+    fn default() -> Self {
+        Self {
+            watering_window: 24 * 60 * 60,
+            wither_deadline: 3 * 24 * 60 * 60,
+            stage_growth_time: 0,
+            fertilizer_boost: 12 * 60 * 60,
+        }
+    }
+}
+
+impl GrowthConfig {
+    /// An equipped tool waters more efficiently, so each watering lasts twice as long
+    pub fn with_tool(self, tool_equipped: bool) -> Self {
+        if !tool_equipped {
+            return self;
+        }
+
+        Self {
+            watering_window: self.watering_window.saturating_mul(2),
+            wither_deadline: self.wither_deadline.saturating_mul(2),
+            ..self
+        }
+    }
+}
+
+/// What is planted in each crop slot of a field, for wallets and indexers to render the farm, and
+/// the soil of each slot
+#[account]
+#[derive(Debug)]
+pub struct FieldState {
+    bump: u8,
+    field_mint: Pubkey,
+    slots: [FieldSlot; 6],
+    soil: [SoilSlot; 6],
+}
+
+impl FieldState {
+    // 8 discriminator + 1 bump + 32 field_mint + 6 slots + 6 soil
+    pub const SPACE: usize = 8 + 1 + 32 + 6 * FieldSlot::SPACE + 6 * SoilSlot::SPACE;
+
+    fn slot_index(crop_slot: &str) -> Result<usize> {
+        Ok(CROP_SLOTS
+            .iter()
+            .position(|slot| *slot == crop_slot)
+            .ok_or(errors::FarmerHouseError::InvalidCropSlot)?)
+    }
+
+    pub fn slot(&self, crop_slot: &str) -> Result<&FieldSlot> {
+        Ok(&self.slots[Self::slot_index(crop_slot)?])
+    }
+
+    pub fn slot_mut(&mut self, crop_slot: &str) -> Result<&mut FieldSlot> {
+        Ok(&mut self.slots[Self::slot_index(crop_slot)?])
+    }
+
+    pub fn soil(&self, crop_slot: &str) -> Result<&SoilSlot> {
+        Ok(&self.soil[Self::slot_index(crop_slot)?])
+    }
+
+    pub fn soil_mut(&mut self, crop_slot: &str) -> Result<&mut SoilSlot> {
+        Ok(&mut self.soil[Self::slot_index(crop_slot)?])
+    }
+
+    /// The species of the crops growing in the slots next to `crop_slot`
+    pub fn neighbour_species(&self, crop_slot: &str) -> Vec<u8> {
+        neighbouring_slots(crop_slot)
+            .into_iter()
+            .filter_map(|neighbour| {
+                let index = CROP_SLOTS.iter().position(|slot| *slot == neighbour)?;
+                let slot = &self.slots[index];
+                slot.stage.map(|_| slot.species_id)
+            })
+            .collect()
+    }
+}
+
+/// An empty slot has no stage
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FieldSlot {
+    pub crop_mint: Pubkey,
+    pub species_id: u8,
+    pub stage: Option<CropStage>,
+}
+
+impl FieldSlot {
+    // 32 crop_mint + 1 species_id + 2 stage
+    pub const SPACE: usize = 32 + 1 + 2;
+}
+
+/// Harvests deplete the soil of a slot, planting a different species than the last one restores it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoilSlot {
+    pub fertility: u8,
+    /// None until a crop is first planted in the slot
+    pub last_species_id: Option<u8>,
+}
+
+impl Default for SoilSlot {
+    fn default() -> Self {
+        Self {
+            fertility: MAX_FERTILITY,
+            last_species_id: None,
+        }
+    }
+}
+
+impl SoilSlot {
+    // 1 fertility + 2 last_species_id
+    pub const SPACE: usize = 1 + 2;
+
+    pub fn plant(&mut self, species_id: u8) {
+        if matches!(self.last_species_id, Some(last) if last != species_id) {
+            self.fertility = self
+                .fertility
+                .saturating_add(ROTATION_RECOVERY)
+                .min(MAX_FERTILITY);
+        }
+        self.last_species_id = Some(species_id);
+    }
+
+    pub fn harvest(&mut self) {
+        self.fertility = self.fertility.saturating_sub(HARVEST_DEPLETION);
+    }
+
+    /// Depleted soil takes up to half again as long to grow a crop
+    pub fn growth_time(&self, stage_growth_time: i64) -> i64 {
+        let max = MAX_FERTILITY as i64;
+        stage_growth_time.saturating_mul(3 * max - self.fertility as i64) / (2 * max)
+    }
+
+    /// Depleted soil yields down to half the produce, a crop always yields at least one unit
+    pub fn yield_of(&self, base_yield: u64) -> u64 {
+        let max = MAX_FERTILITY as u64;
+        (base_yield.saturating_mul(max + self.fertility as u64) / (2 * max)).max(1)
+    }
+}
+
+#[account]
+#[derive(Debug)]
+pub struct CropState {
+    bump: u8,
+    crop_mint: Pubkey,
+    field_mint: Pubkey,
+    species_id: u8,
+    last_cared_at: i64,
+    ready_at: i64,
+    on_time_waterings: u8,
+    missed_windows: u8,
+    /// Slot whose hash decides the event of the harvest, 0 when the crop was planted without events
+    reveal_slot: u64,
+    /// The hash of `reveal_slot`, recorded by the first call after it while the SlotHashes sysvar holds it
+    revealed_hash: Option<[u8; 32]>,
+    /// Whether a tool stayed equipped in the field from the planting through every watering
+    tool_equipped: bool,
+    /// Whether the crop mutated as it ripened, which alone earns it the sell price of its mutation
+    mutated: bool,
+    grade: Option<CropGrade>,
+}
+
+impl CropState {
+    // 8 discriminator + 1 bump + 32 crop_mint + 32 field_mint + 1 species_id + 8 last_cared_at
+    // + 8 ready_at + 1 on_time_waterings + 1 missed_windows + 8 reveal_slot + 33 revealed_hash
+    // + 1 tool_equipped + 1 mutated + 2 grade
+    pub const SPACE: usize = 8 + 1 + 32 + 32 + 1 + 8 + 8 + 1 + 1 + 8 + 33 + 1 + 1 + 2;
+
+    /// An unripe crop withers once it goes longer than the wither deadline without water
+    pub fn is_withered(&self, now: i64, wither_deadline: i64) -> bool {
+        now.saturating_sub(self.last_cared_at) > wither_deadline
+    }
+
+    /// Records a watering, counting it as on time when it happens inside the watering window
+    pub fn record_watering(&mut self, now: i64, watering_window: i64) {
+        if now.saturating_sub(self.last_cared_at) <= watering_window {
+            self.on_time_waterings = self.on_time_waterings.saturating_add(1);
+        } else {
+            self.missed_windows = self.missed_windows.saturating_add(1);
+        }
+        self.last_cared_at = now;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CropGrade {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl CropGrade {
+    pub fn from_care(missed_windows: u8) -> Self {
+        match missed_windows {
+            0 => CropGrade::Gold,
+            1 => CropGrade::Silver,
+            _ => CropGrade::Bronze,
+        }
+    }
+
+    /// The next grade up, used for the extra yield of an equipped tool
+    pub fn upgraded(&self) -> Self {
+        match self {
+            CropGrade::Bronze => CropGrade::Silver,
+            CropGrade::Silver | CropGrade::Gold => CropGrade::Gold,
+        }
+    }
+
+    /// Sell price multiplier in basis points
+    pub fn price_multiplier_bps(&self) -> u64 {
+        match self {
+            CropGrade::Bronze => 10000,
+            CropGrade::Silver => 12500,
+            CropGrade::Gold => 15000,
+        }
+    }
+
+    /// What a crop worth `price` sells for with this grade, failing rather than overflowing
+    pub fn price_of(&self, price: u64) -> Result<u64> {
+        u64::try_from(price as u128 * self.price_multiplier_bps() as u128 / 10000)
+            .map_err(|_| error!(errors::FarmerHouseError::AmountMismatch))
+    }
 }
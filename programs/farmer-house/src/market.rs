@@ -0,0 +1,343 @@
+use {
+    crate::{
+        errors::*, initialized, program::FarmerHouse as program_farmer_house, CropState, FarmsPda,
+        Listing, Species,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{associated_token, token},
+    mpl_token_metadata::{
+        assertions::collection::assert_master_edition,
+        state::{Metadata, TokenMetadataAccount},
+        utils::assert_derivation,
+        ID as TOKEN_METADATA_ID,
+    },
+    mpl_trifle::{state::TRIFLE_SEED, ID as TRIFLE_PROGRAM_ID},
+};
+
+/// Lists a field or crop of the user for `price`, moving it into the escrow of the listing until it is
+/// bought or cancelled
+pub fn create_listing(ctx: Context<CreateListing>, price: u64) -> Result<()> {
+    require!(price > 0, FarmerHouseError::InvalidListing);
+
+    // Only fields and crops of the farm can be listed, the collections the shop buys back
+    let metadata_info = Metadata::from_account_info(&ctx.accounts.nft_metadata.to_account_info())?;
+    require_keys_eq!(
+        metadata_info.mint,
+        ctx.accounts.nft_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+    assert_master_edition(&metadata_info, &ctx.accounts.nft_master_edition)?;
+    let collection = metadata_info
+        .collection
+        .as_ref()
+        .ok_or(FarmerHouseError::CollectionMismatch)?
+        .key;
+
+    // Anyone can mint an NFT naming these collections, so fields have to come with the Trifle the shop
+    // created for them and crops with the CropState of their planting
+    let farms_pda_info = &ctx.accounts.farms_pda;
+    if collection == farms_pda_info.field_collection_address {
+        assert_derivation(
+            &TRIFLE_PROGRAM_ID,
+            &ctx.accounts.trifle_account,
+            &[
+                TRIFLE_SEED.as_bytes(),
+                ctx.accounts.nft_mint.key().as_ref(),
+                farms_pda_info.key().as_ref(),
+            ],
+        )?;
+        require!(
+            !ctx.accounts.trifle_account.data_is_empty(),
+            FarmerHouseError::TrifleMismatch
+        );
+    } else {
+        let crop_state_info = initialized::<CropState>(&ctx.accounts.crop_state)?
+            .ok_or(FarmerHouseError::CollectionMismatch)?;
+        let species = farms_pda_info
+            .species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
+        require!(
+            species.stage_of(&collection).is_some() || crop_state_info.mutated,
+            FarmerHouseError::CollectionMismatch
+        );
+    }
+
+    let listing_info = &mut ctx.accounts.listing;
+    listing_info.bump = *ctx.bumps.get("listing").unwrap();
+    listing_info.seller = ctx.accounts.user_account.key();
+    listing_info.mint = ctx.accounts.nft_mint.key();
+    listing_info.price = price;
+
+    // Transfer the NFT from ATA of user to ATA of the listing
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.nft_ata_source.to_account_info(),
+                to: ctx.accounts.nft_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    msg!("Listed for {}", price);
+
+    Ok(())
+}
+
+/// Buys a listed NFT, paying the seller the price less the market fee, which goes to the treasury of
+/// the shop. `price` is the price the buyer agreed to, so a listing relisted for more can't charge it.
+pub fn buy_listing(ctx: Context<BuyListing>, price: u64) -> Result<()> {
+    let listing_info = &ctx.accounts.listing;
+    require_eq!(price, listing_info.price, FarmerHouseError::ListingMismatch);
+    let fee = ctx.accounts.farms_pda.prices.market_fee(price);
+
+    // Transfer spl token from ATA of the buyer to ATA of the seller, and the fee to ATA of farmsPda
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.spl_ata_source.to_account_info(),
+                to: ctx.accounts.seller_spl_ata.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        price - fee,
+    )?;
+    if fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.spl_ata_source.to_account_info(),
+                    to: ctx.accounts.treasury_spl_ata.to_account_info(),
+                    authority: ctx.accounts.user_account.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let farms_pda = ctx.accounts.farms_pda.key();
+    let nft_mint = ctx.accounts.nft_mint.key();
+    let listing_seeds: &[&[u8]] = &[
+        b"farmer-house-listing",
+        farms_pda.as_ref(),
+        nft_mint.as_ref(),
+        &[listing_info.bump],
+    ];
+
+    // Transfer the NFT from ATA of the listing to ATA of the buyer
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.nft_ata_source.to_account_info(),
+                to: ctx.accounts.nft_ata_destination.to_account_info(),
+                authority: listing_info.to_account_info(),
+            },
+            &[listing_seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.nft_ata_source.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: listing_info.to_account_info(),
+        },
+        &[listing_seeds],
+    ))?;
+    msg!("Listing bought for {}, {} of it in fees", price, fee);
+
+    Ok(())
+}
+
+/// Takes a listed NFT off the market, back to the seller
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    let listing_info = &ctx.accounts.listing;
+    require_keys_eq!(
+        listing_info.seller,
+        ctx.accounts.user_account.key(),
+        FarmerHouseError::OwnerMismatch
+    );
+
+    let farms_pda = ctx.accounts.farms_pda.key();
+    let nft_mint = ctx.accounts.nft_mint.key();
+    let listing_seeds: &[&[u8]] = &[
+        b"farmer-house-listing",
+        farms_pda.as_ref(),
+        nft_mint.as_ref(),
+        &[listing_info.bump],
+    ];
+
+    // Transfer the NFT from ATA of the listing back to ATA of the seller
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.nft_ata_source.to_account_info(),
+                to: ctx.accounts.nft_ata_destination.to_account_info(),
+                authority: listing_info.to_account_info(),
+            },
+            &[listing_seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.nft_ata_source.to_account_info(),
+            destination: ctx.accounts.user_account.to_account_info(),
+            authority: listing_info.to_account_info(),
+        },
+        &[listing_seeds],
+    ))?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateListing<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    pub nft_mint: Box<Account<'info, token::Mint>>,
+    /// CHECK: create_listing checks this is the metadata of the NFT
+    #[account(owner = TOKEN_METADATA_ID)]
+    pub nft_metadata: UncheckedAccount<'info>,
+    /// CHECK: create_listing checks this is the master edition of the NFT
+    pub nft_master_edition: UncheckedAccount<'info>,
+    /// CHECK: create_listing checks this is the Trifle of the field, only read when listing a field
+    pub trifle_account: UncheckedAccount<'info>,
+    /// CHECK: The CropState PDA of the crop, required when listing a crop
+    #[account(seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), nft_mint.key().as_ref()], bump)]
+    pub crop_state: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user_account
+    )]
+    pub nft_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init,
+        payer = user_account,
+        space = Listing::SPACE,
+        seeds = [b"farmer-house-listing", farms_pda.key().as_ref(), nft_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = nft_mint,
+        associated_token::authority = listing
+    )]
+    pub nft_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct BuyListing<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+    #[account(mut, address = listing.seller @ FarmerHouseError::ListingMismatch)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"farmer-house-listing", farms_pda.key().as_ref(), nft_mint.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    pub nft_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = listing
+    )]
+    pub nft_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user_account
+    )]
+    pub nft_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = user_account,
+    )]
+    pub spl_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = seller,
+    )]
+    pub seller_spl_ata: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = farms_pda,
+    )]
+    pub treasury_spl_ata: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-listing", farms_pda.key().as_ref(), nft_mint.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    pub nft_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = listing
+    )]
+    pub nft_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user_account,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user_account
+    )]
+    pub nft_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -1,14 +1,25 @@
 use mpl_token_metadata::assertions::collection::assert_master_edition;
 
 use {
-    crate::{errors::*, program::FarmerHouse as program_farmer_house, FarmsPda},
+    crate::{
+        errors::*, program::FarmerHouse as program_farmer_house, Coop, CropState, FarmsPda,
+        FieldVault, Mutation, Produce, Species,
+    },
     anchor_lang::{
         prelude::*,
         solana_program::{program::invoke_signed, sysvar::instructions::ID as INSTRUCTIONS_ID},
     },
     anchor_spl::{associated_token, token},
-    mpl_token_metadata::{state::{Metadata, TokenMetadataAccount}, ID as TOKEN_METADATA_ID},
-    mpl_trifle::{instruction as trifle_instruction, ID as TRIFLE_PROGRAM_ID},
+    mpl_token_metadata::{
+        state::{Metadata, TokenMetadataAccount},
+        utils::assert_derivation,
+        ID as TOKEN_METADATA_ID,
+    },
+    mpl_trifle::{
+        instruction as trifle_instruction,
+        state::{trifle::Trifle, SolanaAccount, TRIFLE_SEED},
+        ID as TRIFLE_PROGRAM_ID,
+    },
 };
 
 pub fn buy_field(ctx: Context<BuyField>) -> Result<()> {
@@ -145,7 +156,7 @@ pub fn buy_field(ctx: Context<BuyField>) -> Result<()> {
 
     // Assert ATA sources have enough tokens to transfer out
     assert!(
-        ctx.accounts.spl_ata_source.amount >= 70000000,
+        ctx.accounts.spl_ata_source.amount >= farms_pda_info.prices.field,
         "{}",
         FarmerHouseError::AmountMismatch
     );
@@ -185,7 +196,7 @@ pub fn buy_field(ctx: Context<BuyField>) -> Result<()> {
                 authority: ctx.accounts.user_account.to_account_info(),
             },
         ),
-        70000000,
+        ctx.accounts.farms_pda.prices.field,
     )?;
 
     // Transfer fieldNFT from ATA of FarmsPda to ATA of user
@@ -200,6 +211,7 @@ pub fn buy_field(ctx: Context<BuyField>) -> Result<()> {
             &[&[
                 b"farmer-house-farms",
                 ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
                 &[ctx.accounts.farms_pda.bump],
             ]],
         ),
@@ -240,6 +252,7 @@ pub fn buy_field(ctx: Context<BuyField>) -> Result<()> {
             &[&[
                 b"farmer-house-farms",
                 ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
                 &[ctx.accounts.farms_pda.bump],
             ]],
         )?;
@@ -298,6 +311,8 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
 
     let binding = Metadata::from_account_info(&ctx.accounts.seed_metadata.to_account_info());
     let metadata_info = binding.as_ref().unwrap();
+    let species =
+        farms_pda_info.species_collections(ctx.accounts.species.as_deref().map(|species| &**species));
 
     assert_eq!(
         metadata_info
@@ -305,7 +320,7 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
             .as_ref()
             .unwrap()
             .key,
-        farms_pda_info.tomato_seed_collection_address,
+        species.seed,
         "{}",
         FarmerHouseError::CollectionMismatch
     );
@@ -345,7 +360,7 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
     
     // Assert ATA sources have enough tokens to transfer out
     assert!(
-        ctx.accounts.spl_ata_source.amount >= 2000000,
+        ctx.accounts.spl_ata_source.amount >= farms_pda_info.prices.seed,
         "{}",
         FarmerHouseError::AmountMismatch
     );
@@ -385,7 +400,7 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
                 authority: ctx.accounts.user_account.to_account_info(),
             },
         ),
-        2000000,
+        ctx.accounts.farms_pda.prices.seed,
     )?;
 
     // Transfer seedNFT from ATA of FarmsPda to ATA of user
@@ -400,6 +415,7 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
             &[&[
                 b"farmer-house-farms",
                 ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
                 &[ctx.accounts.farms_pda.bump],
             ]],
         ),
@@ -409,9 +425,109 @@ pub fn buy_seed(ctx: Context<BuySeed>) -> Result<()> {
     Ok(())
 }
 
-pub fn sell_crop(ctx: Context<SellCrop>) -> Result<()> {
+pub fn buy_fertilizer(ctx: Context<BuyFertilizer>, amount: u64) -> Result<()> {
+    let farms_pda_info = &ctx.accounts.farms_pda;
+
+    require!(amount > 0, FarmerHouseError::AmountMismatch);
+    let price = amount
+        .checked_mul(farms_pda_info.prices.fertilizer)
+        .ok_or(FarmerHouseError::AmountMismatch)?;
+
+    //Assert spl_mint and it's associated accounts are correctly set up
+    require_eq!(
+        ctx.accounts.spl_mint.key(),
+        farms_pda_info.spl_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+    require_eq!(
+        ctx.accounts.spl_ata_source.mint,
+        farms_pda_info.spl_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+    require_eq!(
+        ctx.accounts.spl_ata_destination.mint,
+        farms_pda_info.spl_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+
+    // Assert fertilizer_mint and it's associated accounts are correctly set up
+    require_eq!(
+        ctx.accounts.fertilizer_mint.key(),
+        farms_pda_info.fertilizer_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+    require_eq!(
+        ctx.accounts.fertilizer_ata_source.mint,
+        farms_pda_info.fertilizer_mint_address,
+        FarmerHouseError::MintMismatch
+    );
+
+    // Assert ATA sources have enough tokens to transfer out
+    require!(
+        ctx.accounts.spl_ata_source.amount >= price,
+        FarmerHouseError::AmountMismatch
+    );
+    require!(
+        ctx.accounts.fertilizer_ata_source.amount >= amount,
+        FarmerHouseError::AmountMismatch
+    );
+
+    if *ctx.accounts.fertilizer_ata_destination.owner != token::spl_token::ID
+        && ctx.accounts.fertilizer_ata_destination.lamports() == 0
+    {
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.user_account.to_account_info(),
+                associated_token: ctx.accounts.fertilizer_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+                mint: ctx.accounts.fertilizer_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    // Transfer spl token from ATA of user to ATA of pda
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.spl_ata_source.to_account_info(),
+                to: ctx.accounts.spl_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    // Transfer fertilizer from ATA of FarmsPda to ATA of user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.fertilizer_ata_source.to_account_info(),
+                to: ctx.accounts.fertilizer_ata_destination.to_account_info(),
+                authority: ctx.accounts.farms_pda.to_account_info(),
+            },
+            &[&[
+                b"farmer-house-farms",
+                ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
+                &[ctx.accounts.farms_pda.bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+pub fn sell_crop<'info>(
+    mut ctx: Context<'_, '_, '_, 'info, SellCrop<'info>>,
+    amount: u64,
+) -> Result<()> {
     let farms_pda_info = &ctx.accounts.farms_pda;
-    let metadata_info = Metadata::from_account_info(&ctx.accounts.crop_metadata.to_account_info());
 
     // Confirm token_program is owner of associated token accounts
     assert_eq!(
@@ -455,65 +571,273 @@ pub fn sell_crop(ctx: Context<SellCrop>) -> Result<()> {
         FarmerHouseError::MintMismatch
     );
 
+    // The crop or produce goes to the shop, paying a seller who passes their own account would let
+    // them keep it
+    require_keys_eq!(
+        ctx.accounts.crop_ata_destination.owner,
+        farms_pda_info.key(),
+        FarmerHouseError::OwnerMismatch
+    );
+
+    let price = match &ctx.accounts.produce {
+        // Produce is sold by amount, its grade already went into how much of it the harvest yielded
+        Some(produce_info) => {
+            require_keys_eq!(
+                ctx.accounts.crop_ata_source.mint,
+                produce_info.mint,
+                FarmerHouseError::ProduceMismatch
+            );
+            require_keys_eq!(
+                ctx.accounts.crop_ata_destination.mint,
+                produce_info.mint,
+                FarmerHouseError::ProduceMismatch
+            );
+            require!(amount > 0, FarmerHouseError::AmountMismatch);
+            produce_info
+                .config
+                .unit_price
+                .checked_mul(amount)
+                .ok_or(FarmerHouseError::AmountMismatch)?
+        }
+        None => {
+            require_eq!(amount, 1, FarmerHouseError::AmountMismatch);
+            crop_price(ctx.accounts)?
+        }
+    };
+
+    // Assert ATA sources have enough tokens to transfer out
+    assert!(
+        ctx.accounts.spl_ata_source.amount >= price,
+        "{}",
+        FarmerHouseError::AmountMismatch
+    );
+    assert!(
+        ctx.accounts.crop_ata_source.amount >= amount,
+        "{}",
+        FarmerHouseError::AmountMismatch
+    );
+
+    if ctx.accounts.coop.is_some() {
+        return sell_for_coop(&mut ctx, amount, price);
+    }
+
+    // Transfer the crop or produce from ATA of user to ATA of FarmsPda
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.crop_ata_source.to_account_info(),
+                to: ctx.accounts.crop_ata_destination.to_account_info(),
+                authority: ctx.accounts.user_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Transfer spl token from ATA of farmsPda to ATA of user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.spl_ata_source.to_account_info(),
+                to: ctx.accounts.spl_ata_destination.to_account_info(),
+                authority: ctx.accounts.farms_pda.to_account_info(),
+            },
+            &[&[
+                b"farmer-house-farms",
+                ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
+                &[ctx.accounts.farms_pda.bump],
+            ]],
+        ),
+        price,
+    )?;
+
+    Ok(())
+}
+
+/// Sells the harvest of a co-op out of its vault, splitting the proceeds between its members by their
+/// shares. The currency token accounts of the members are passed as `remaining_accounts`, in the order
+/// of the members.
+fn sell_for_coop<'info>(
+    ctx: &mut Context<'_, '_, '_, 'info, SellCrop<'info>>,
+    amount: u64,
+    price: u64,
+) -> Result<()> {
+    let coop_info = ctx
+        .accounts
+        .coop
+        .as_deref_mut()
+        .ok_or(FarmerHouseError::CoopMismatch)?;
+    require!(
+        coop_info.member(&ctx.accounts.user_account.key()).is_some(),
+        FarmerHouseError::NotCoopMember
+    );
+    // The vault only holds the crops the co-op harvested, counted until they are sold
+    coop_info.crops = u32::try_from(amount)
+        .ok()
+        .and_then(|amount| coop_info.crops.checked_sub(amount))
+        .ok_or(FarmerHouseError::AmountMismatch)?;
+    let coop_vault = ctx
+        .accounts
+        .coop_vault
+        .as_ref()
+        .ok_or(FarmerHouseError::CoopMismatch)?;
+    let coop = coop_info.key();
+    require_keys_eq!(
+        coop_vault.key(),
+        coop_info.vault(&coop),
+        FarmerHouseError::CoopMismatch
+    );
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        coop_info.members.len(),
+        FarmerHouseError::CoopMismatch
+    );
+
+    // Transfer the crop from ATA of the co-op vault to ATA of FarmsPda
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.crop_ata_source.to_account_info(),
+                to: ctx.accounts.crop_ata_destination.to_account_info(),
+                authority: coop_vault.to_account_info(),
+            },
+            &[&[b"farmer-house-coop-vault", coop.as_ref(), &[coop_info.vault_bump()]]],
+        ),
+        amount,
+    )?;
+
+    // Transfer each member's part of the spl token from ATA of farmsPda to ATA of the member
+    for ((member, spl_ata), part) in coop_info
+        .members
+        .iter()
+        .zip(ctx.remaining_accounts)
+        .zip(coop_info.split(price))
+    {
+        let spl_ata_info = Account::<token::TokenAccount>::try_from(spl_ata)?;
+        require_keys_eq!(
+            spl_ata_info.owner,
+            member.wallet,
+            FarmerHouseError::CoopMismatch
+        );
+        require_keys_eq!(
+            spl_ata_info.mint,
+            ctx.accounts.spl_mint.key(),
+            FarmerHouseError::MintMismatch
+        );
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.spl_ata_source.to_account_info(),
+                    to: spl_ata.clone(),
+                    authority: ctx.accounts.farms_pda.to_account_info(),
+                },
+                &[&[
+                    b"farmer-house-farms",
+                    ctx.accounts.farmer_house_program.key().as_ref(),
+                    &ctx.accounts.farms_pda.instance_seed(),
+                    &[ctx.accounts.farms_pda.bump],
+                ]],
+            ),
+            part,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// What the shop pays for a crop NFT, by the stage of its collection and the grade it was harvested with
+fn crop_price(accounts: &SellCrop) -> Result<u64> {
+    let metadata_info = Metadata::from_account_info(&accounts.crop_metadata.to_account_info());
+
     assert_eq!(
         &metadata_info.as_ref().unwrap().mint,
-        &ctx.accounts.crop_ata_source.mint,
+        &accounts.crop_ata_source.mint,
         "{}",
         FarmerHouseError::MintMismatch
     );
 
     assert_eq!(
         &metadata_info.as_ref().unwrap().mint,
-        &ctx.accounts.crop_ata_destination.mint,
+        &accounts.crop_ata_destination.mint,
         "{}",
         FarmerHouseError::MintMismatch
     );
 
-    let price: u64;
-
-    if metadata_info
-        .as_ref()
-        .unwrap()
-        .collection
-        .as_ref()
-        .unwrap()
-        .key
-        == farms_pda_info.tomato_sappling_collection_address
-    {
-        price = 3500000;
-    } else if metadata_info
+    let collection = metadata_info
         .as_ref()
         .unwrap()
         .collection
         .as_ref()
         .unwrap()
-        .key
-        == farms_pda_info.ripe_tomato_collection_address
-    {
-        price = 7000000;
-    } else {
-        price = 2000000;
+        .key;
+
+    let crop_state_info = accounts.crop_state.as_deref();
+    if let Some(crop_state_info) = crop_state_info {
+        require_eq!(
+            crop_state_info.crop_mint,
+            metadata_info.as_ref().unwrap().mint,
+            FarmerHouseError::MintMismatch
+        );
     }
-    
-    // Assert ATA sources have enough tokens to transfer out
-    assert!(
-        ctx.accounts.spl_ata_source.amount >= price,
-        "{}",
-        FarmerHouseError::AmountMismatch
+
+    accounts.farms_pda.crop_price(
+        accounts.species.as_deref().map(|species| &**species),
+        &collection,
+        crop_state_info.map(|crop_state_info| &**crop_state_info),
+        accounts.mutation.as_deref().map(|mutation| &**mutation),
+    )
+}
+
+/// Buys an empty field back into the shop's inventory, so it can be sold to the next player
+pub fn sell_field(ctx: Context<SellField>) -> Result<()> {
+    let metadata_info = Metadata::from_account_info(&ctx.accounts.field_metadata.to_account_info())?;
+    require!(
+        matches!(
+            &metadata_info.collection,
+            Some(collection) if collection.key == ctx.accounts.farms_pda.field_collection_address
+        ),
+        FarmerHouseError::CollectionMismatch
     );
-    assert!(
-        ctx.accounts.crop_ata_source.amount >= 1,
-        "{}",
+    require_keys_eq!(
+        metadata_info.mint,
+        ctx.accounts.field_mint.key(),
+        FarmerHouseError::MintMismatch
+    );
+
+    // The field's Trifle goes with it, so nothing can be left growing or equipped in it
+    assert_derivation(
+        &TRIFLE_PROGRAM_ID,
+        &ctx.accounts.trifle_account,
+        &[
+            TRIFLE_SEED.as_bytes(),
+            ctx.accounts.field_mint.key().as_ref(),
+            ctx.accounts.farms_pda.key().as_ref(),
+        ],
+    )?;
+    let trifle = Trifle::from_account_info(&ctx.accounts.trifle_account.to_account_info())?;
+    require!(
+        trifle.tokens.values().flatten().all(|token| token.amount == 0),
+        FarmerHouseError::FieldNotEmpty
+    );
+
+    let price = ctx.accounts.farms_pda.prices.field_buyback;
+    require!(
+        ctx.accounts.spl_ata_source.amount >= price,
         FarmerHouseError::AmountMismatch
     );
 
-    // Transfer cropNFT from ATA of FarmsPda to ATA of user
+    // Transfer fieldNFT from ATA of user to ATA of FarmsPda
     token::transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             token::Transfer {
-                from: ctx.accounts.crop_ata_source.to_account_info(),
-                to: ctx.accounts.crop_ata_destination.to_account_info(),
+                from: ctx.accounts.field_ata_source.to_account_info(),
+                to: ctx.accounts.field_ata_destination.to_account_info(),
                 authority: ctx.accounts.user_account.to_account_info(),
             },
         ),
@@ -532,18 +856,20 @@ pub fn sell_crop(ctx: Context<SellCrop>) -> Result<()> {
             &[&[
                 b"farmer-house-farms",
                 ctx.accounts.farmer_house_program.key().as_ref(),
+                &ctx.accounts.farms_pda.instance_seed(),
                 &[ctx.accounts.farms_pda.bump],
             ]],
         ),
         price,
     )?;
+    msg!("Field sold for {}", price);
 
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct BuyField<'info> {
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
     pub farms_pda: Account<'info, FarmsPda>,
 
     #[account(mut)]
@@ -593,7 +919,7 @@ pub struct BuyField<'info> {
 
 #[derive(Accounts)]
 pub struct BuySeed<'info> {
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
     pub farms_pda: Account<'info, FarmsPda>,
 
     #[account(mut)]
@@ -613,6 +939,35 @@ pub struct BuySeed<'info> {
     pub seed_mint: Box<Account<'info, token::Mint>>,
     /// CHECK: 
     pub seed_metadata: UncheckedAccount<'info>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, token::Token>,
+    pub system_program: Program<'info, System>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
+
+#[derive(Accounts)]
+pub struct BuyFertilizer<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Account<'info, FarmsPda>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(mut)]
+    pub spl_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut, token::authority = farms_pda)]
+    pub spl_ata_destination: Box<Account<'info, token::TokenAccount>>,
+    pub spl_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(mut)]
+    pub fertilizer_ata_source: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: We're about to create this
+    #[account(mut)]
+    pub fertilizer_ata_destination: UncheckedAccount<'info>,
+    pub fertilizer_mint: Box<Account<'info, token::Mint>>,
 
     pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
     pub token_program: Program<'info, token::Token>,
@@ -622,7 +977,7 @@ pub struct BuySeed<'info> {
 
 #[derive(Accounts)]
 pub struct SellCrop<'info> {
-    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref()], bump = farms_pda.bump)]
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
     pub farms_pda: Account<'info, FarmsPda>,
 
     #[account(mut)]
@@ -641,9 +996,69 @@ pub struct SellCrop<'info> {
     /// CHECK:
     #[account(mut)]
     pub crop_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = user_account,
+        seeds = [b"farmer-house-crop", farms_pda.key().as_ref(), crop_state.crop_mint.as_ref()],
+        bump = crop_state.bump
+    )]
+    pub crop_state: Option<Box<Account<'info, CropState>>>,
+    #[account(seeds = [b"farmer-house-species", farms_pda.key().as_ref(), &[species.species_id]], bump = species.bump)]
+    pub species: Option<Box<Account<'info, Species>>>,
+    /// Sells produce of the species instead of a crop NFT
+    #[account(seeds = [b"farmer-house-produce", farms_pda.key().as_ref(), &[produce.species_id]], bump = produce.bump)]
+    pub produce: Option<Box<Account<'info, Produce>>>,
+    #[account(seeds = [b"farmer-house-mutation", farms_pda.key().as_ref(), &[mutation.species_id]], bump = mutation.bump)]
+    pub mutation: Option<Box<Account<'info, Mutation>>>,
+    /// Sells a harvest of the co-op out of its vault
+    #[account(mut, seeds = [b"farmer-house-coop", farms_pda.key().as_ref(), coop.field_mint.as_ref()], bump = coop.bump)]
+    pub coop: Option<Box<Account<'info, Coop>>>,
+    /// CHECK: sell_crop checks this is the vault of the co-op, only needed with coop
+    pub coop_vault: Option<UncheckedAccount<'info>>,
 
     pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
     pub token_program: Program<'info, token::Token>,
     pub system_program: Program<'info, System>,
     pub farmer_house_program: Program<'info, program_farmer_house>,
 }
+
+#[derive(Accounts)]
+pub struct SellField<'info> {
+    #[account(seeds = [b"farmer-house-farms", farmer_house_program.key().as_ref(), &farms_pda.instance_seed()], bump = farms_pda.bump)]
+    pub farms_pda: Box<Account<'info, FarmsPda>>,
+
+    #[account(mut)]
+    pub user_account: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = farms_pda.spl_mint_address,
+        token::authority = farms_pda,
+    )]
+    pub spl_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(mut, token::mint = farms_pda.spl_mint_address)]
+    pub spl_ata_destination: Box<Account<'info, token::TokenAccount>>,
+
+    pub field_mint: Box<Account<'info, token::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = user_account
+    )]
+    pub field_ata_source: Box<Account<'info, token::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = field_mint,
+        associated_token::authority = farms_pda
+    )]
+    pub field_ata_destination: Box<Account<'info, token::TokenAccount>>,
+    /// CHECK: sell_field checks this is the metadata of the field
+    #[account(owner = TOKEN_METADATA_ID)]
+    pub field_metadata: UncheckedAccount<'info>,
+    /// CHECK: sell_field checks this is the Trifle of the field
+    #[account(owner = TRIFLE_PROGRAM_ID)]
+    pub trifle_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, token::Token>,
+    pub farmer_house_program: Program<'info, program_farmer_house>,
+}
@@ -0,0 +1,125 @@
+use {
+    crate::{errors::*, Coop, Farmhand, FieldVault, Lease, LEASE_VAULT_FLOAT},
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            instruction::Instruction,
+            program::{invoke, invoke_signed},
+        },
+        system_program,
+    },
+};
+
+/// The account Trifle takes as the holder of a field: the user, or the vault of the lease they took, of
+/// the farmhand they act through or of the co-op they are a member of
+pub(crate) struct FieldHolder {
+    pub key: Pubkey,
+    /// Seed, account and bump the vault is derived from, when a vault holds the field
+    vault: Option<(&'static [u8], Pubkey, u8)>,
+    mismatch: FarmerHouseError,
+}
+
+impl FieldHolder {
+    /// The holder of the field for `user` taking `action` on it, checking they are allowed to when the
+    /// field is held by a lease, farmhand or co-op
+    pub fn of(
+        user: &Pubkey,
+        lease: Option<&Account<Lease>>,
+        farmhand: Option<&Account<Farmhand>>,
+        coop: Option<&Account<Coop>>,
+        action: u8,
+    ) -> Result<Self> {
+        let now = Clock::get()?.unix_timestamp;
+        match (lease, farmhand, coop) {
+            (Some(lease_info), None, None) => {
+                require!(
+                    lease_info.is_held_by(user, now),
+                    FarmerHouseError::LeaseExpired
+                );
+                Ok(Self::vault_of(lease_info, FarmerHouseError::LeaseMismatch))
+            }
+            (None, Some(farmhand_info), None) => {
+                require!(
+                    farmhand_info.allows(user, action, now),
+                    FarmerHouseError::FarmhandNotAllowed
+                );
+                Ok(Self::vault_of(farmhand_info, FarmerHouseError::FarmhandMismatch))
+            }
+            (None, None, Some(coop_info)) => {
+                require!(
+                    coop_info.member(user).is_some(),
+                    FarmerHouseError::NotCoopMember
+                );
+                Ok(Self::vault_of(coop_info, FarmerHouseError::CoopMismatch))
+            }
+            (None, None, None) => Ok(Self {
+                key: *user,
+                vault: None,
+                mismatch: FarmerHouseError::OwnerMismatch,
+            }),
+            // A field is held by a single vault at a time
+            _ => err!(FarmerHouseError::OwnerMismatch),
+        }
+    }
+
+    fn vault_of<T>(custodian: &Account<T>, mismatch: FarmerHouseError) -> Self
+    where
+        T: FieldVault + AccountSerialize + AccountDeserialize + Owner + Clone,
+    {
+        Self {
+            key: custodian.vault(&custodian.key()),
+            vault: Some((T::VAULT_SEED, custodian.key(), custodian.vault_bump())),
+            mismatch,
+        }
+    }
+
+    pub fn is_vault(&self) -> bool {
+        self.vault.is_some()
+    }
+
+    /// The account of the holder, topping the vault up from the user so it can pay for the transfer
+    pub fn fund<'info>(
+        &self,
+        user_account: &Signer<'info>,
+        vault: Option<&UncheckedAccount<'info>>,
+        system_program: &Program<'info, System>,
+    ) -> Result<AccountInfo<'info>> {
+        if self.vault.is_none() {
+            return Ok(user_account.to_account_info());
+        }
+        let vault = vault.ok_or(self.mismatch)?;
+        require_keys_eq!(vault.key(), self.key, self.mismatch);
+
+        let top_up = LEASE_VAULT_FLOAT.saturating_sub(vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: user_account.to_account_info(),
+                        to: vault.to_account_info(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        Ok(vault.to_account_info())
+    }
+
+    /// Invokes a Trifle instruction, signed by the vault when it holds the field
+    pub fn invoke(&self, instruction: &Instruction, account_infos: &[AccountInfo]) -> Result<()> {
+        match &self.vault {
+            Some((seed, custodian, bump)) => {
+                invoke_signed(
+                    instruction,
+                    account_infos,
+                    &[&[seed, custodian.as_ref(), &[*bump]]],
+                )?;
+            }
+            None => invoke(instruction, account_infos)?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! Decodes what is growing in a field from its fetched accounts, for clients to render the farm.
+//!
+//! Only built with the `view` feature. Stages and sell prices follow the rules `water` and `sell_crop`
+//! apply on-chain, so a client shows what the program would do with the crop.
+
+use {
+    crate::{
+        errors::FarmerHouseError, farm::tool_equipped, CropStage, CropState, FarmsPda, Mutation,
+        Produce, Species, CROP_SLOTS, TOMATO_SPECIES_ID,
+    },
+    anchor_lang::{error::ErrorCode, prelude::*},
+    mpl_token_metadata::state::{Metadata, TokenMetadataAccount},
+    mpl_trifle::state::{trifle::Trifle, SolanaAccount},
+    std::collections::HashMap,
+};
+
+/// What is planted in a field
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldView {
+    pub field_mint: Pubkey,
+    pub tool_equipped: bool,
+    /// One entry per planted crop slot, in the order of `CROP_SLOTS`
+    pub slots: Vec<SlotView>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotView {
+    pub crop_slot: String,
+    pub crop_mint: Pubkey,
+    pub species_id: u8,
+    /// None when the crop's collection isn't one of its species, `water` rejects such a crop. Mutated
+    /// crops are ripe.
+    pub stage: Option<CropStage>,
+    pub mutated: bool,
+    /// What `sell_crop` pays for the crop once it's back in the user's wallet, none when the species
+    /// has produce, which `harvest_crop` turns the crop into instead
+    pub sell_price: Option<u64>,
+}
+
+/// The registered accounts of the farm's species that the crops of a field may belong to
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpeciesAccounts<'a> {
+    pub species: &'a [Species],
+    pub mutations: &'a [Mutation],
+    /// The produce of the species that have it
+    pub produces: &'a [Produce],
+}
+
+/// The crops planted in each slot of a field, read from the data of its Trifle account. Their
+/// metadata and `CropState` accounts are what `field_view` needs next.
+pub fn planted_crops(trifle_data: &[u8]) -> Result<Vec<(String, Pubkey)>> {
+    let trifle = decode_trifle(trifle_data)?;
+    Ok(CROP_SLOTS
+        .iter()
+        .filter_map(|crop_slot| {
+            planted_in(&trifle, crop_slot).map(|crop_mint| (crop_slot.to_string(), crop_mint))
+        })
+        .collect())
+}
+
+/// Decodes the field from its Trifle account data, the metadata account data and `CropState` of each
+/// planted crop keyed by crop mint, and the accounts of the species the crops may belong to
+pub fn field_view(
+    farms_pda: &FarmsPda,
+    field_mint: Pubkey,
+    trifle_data: &[u8],
+    crop_metadata: &HashMap<Pubkey, Vec<u8>>,
+    crop_states: &HashMap<Pubkey, CropState>,
+    species_accounts: SpeciesAccounts,
+) -> Result<FieldView> {
+    let SpeciesAccounts {
+        species,
+        mutations,
+        produces,
+    } = species_accounts;
+    let trifle = decode_trifle(trifle_data)?;
+
+    let mut slots = vec![];
+    for crop_slot in CROP_SLOTS {
+        let crop_mint = match planted_in(&trifle, crop_slot) {
+            Some(crop_mint) => crop_mint,
+            None => continue,
+        };
+        let crop_state = crop_states
+            .get(&crop_mint)
+            .ok_or(ErrorCode::AccountNotInitialized)?;
+        let metadata = crop_metadata
+            .get(&crop_mint)
+            .ok_or(ErrorCode::AccountNotInitialized)?;
+        let metadata = Metadata::safe_deserialize(metadata)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+
+        // The crop keeps the species it was planted as, the tomato lives in the farms PDA
+        let species_account = if crop_state.species_id == TOMATO_SPECIES_ID {
+            None
+        } else {
+            Some(
+                species
+                    .iter()
+                    .find(|species| species.species_id == crop_state.species_id)
+                    .ok_or(FarmerHouseError::InvalidSpecies)?,
+            )
+        };
+        let collection = metadata.collection.map(|collection| collection.key);
+        // Like `sell_crop`, only a crop its `CropState` flags as mutated counts as one
+        let mutation = mutations.iter().find(|mutation| {
+            crop_state.mutated
+                && mutation.species_id == crop_state.species_id
+                && Some(mutation.config.collection) == collection
+        });
+        let stage = match mutation {
+            Some(_) => Some(CropStage::Ripe),
+            None => collection.and_then(|collection| {
+                farms_pda
+                    .species_collections(species_account)
+                    .stage_of(&collection)
+            }),
+        };
+
+        let sell_price = if produces
+            .iter()
+            .any(|produce| produce.species_id == crop_state.species_id)
+        {
+            None
+        } else {
+            let collection = collection.unwrap_or_default();
+            Some(farms_pda.crop_price(species_account, &collection, Some(crop_state), mutation)?)
+        };
+
+        slots.push(SlotView {
+            crop_slot: crop_slot.to_string(),
+            crop_mint,
+            species_id: crop_state.species_id,
+            stage,
+            mutated: mutation.is_some(),
+            sell_price,
+        });
+    }
+
+    Ok(FieldView {
+        field_mint,
+        tool_equipped: tool_equipped(&trifle),
+        slots,
+    })
+}
+
+fn decode_trifle(trifle_data: &[u8]) -> Result<Trifle> {
+    Trifle::safe_deserialize(trifle_data).map_err(|_| error!(FarmerHouseError::TrifleMismatch))
+}
+
+fn planted_in(trifle: &Trifle, crop_slot: &str) -> Option<Pubkey> {
+    trifle
+        .tokens
+        .get(crop_slot)
+        .and_then(|tokens| tokens.iter().find(|token| token.amount > 0))
+        .map(|token| token.mint)
+}
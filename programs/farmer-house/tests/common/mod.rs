@@ -0,0 +1,865 @@
+//! Shared setup of the integration tests.
+//!
+//! Every test starts a bank with the Metaplex programs bundled in `test-programs/` and FarmerHouse
+//! deployed as an upgradeable program owned by the PAW, so `initialize_farms_pda` can check its
+//! upgrade authority. The FarmerHouse binary comes from `cargo test-sbf`.
+
+// Each test binary only uses part of the harness
+#![allow(dead_code, unused_imports)]
+
+// The instruction builders of `farmer-house-client`, compiled into the tests instead of pulled in as a
+// dev-dependency. The client enables `no-entrypoint` on this crate, which the v1 feature resolver
+// would leak into the program build.
+#[path = "../../../../client/src/lib.rs"]
+pub mod client;
+
+use {
+    anchor_lang::{AccountDeserialize, Discriminator},
+    anchor_spl::token::spl_token::{self, state::Account as TokenAccount, state::Mint},
+    client::Farm,
+    farmer_house::{errors::FarmerHouseError, CropState, FarmsPda},
+    mpl_token_metadata::{
+        instruction::{
+            create_master_edition_v3, create_metadata_accounts_v3, unverify_sized_collection_item,
+            update_metadata_accounts_v2, verify_sized_collection_item,
+        },
+        state::{Collection, CollectionDetails, Creator, Metadata, TokenMetadataAccount},
+    },
+    solana_program_test::{
+        find_file, read_file, BanksClientError, ProgramTest, ProgramTestBanksClientExt,
+        ProgramTestContext,
+    },
+    solana_sdk::{
+        account::Account,
+        bpf_loader,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        compute_budget::ComputeBudgetInstruction,
+        instruction::{Instruction, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{Keypair, Signer},
+        system_instruction, system_program,
+        sysvar::clock::Clock,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account,
+    },
+    std::{collections::HashMap, path::PathBuf},
+};
+
+pub use client::{
+    pda, view, CompanionEffect, CoopMember, CraftInput, CropOptions, CropStage, EventConfig,
+    Farmhand, GrowthConfig, LeaseTerms, MutationConfig, ProduceConfig, RecipeConfig, RecipeInput,
+    ShopPrices, StageCreator, StageMetadataData,
+};
+
+pub const CONSTRAINT_MODEL_NAME: &str = "Basic Farm";
+pub const SCHEMA_URI: &str =
+    "https://fossil-test.fra1.digitaloceanspaces.com/the_search_schema.json";
+pub const METADATA_URI: &str =
+    "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json";
+
+/// What the PAW mints and what the user starts with, in base units of the CurrencyToken
+pub const PAW_CURRENCY: u64 = 1_000_000_000;
+pub const USER_CURRENCY: u64 = 300_000_000;
+pub const SHOP_CURRENCY: u64 = 300_000_000;
+
+pub const PRODUCE_CONFIG: ProduceConfig = ProduceConfig {
+    bronze_yield: 1,
+    silver_yield: 2,
+    gold_yield: 3,
+    unit_price: 3_000_000,
+};
+
+/// A day's lease for 10 CurrencyTokens, giving the owner a fifth of every harvest
+pub const LEASE_TERMS: LeaseTerms = LeaseTerms {
+    fee: 10_000_000,
+    duration: 86_400,
+    share_bps: 2_000,
+};
+
+/// How long the farmhands of the tests are delegated for, a day
+pub const FARMHAND_DURATION: i64 = 86_400;
+
+/// The shares the user and the PAW hold in the co-ops of the tests, three to one
+pub const COOP_SHARES: (u32, u32) = (3, 1);
+
+#[derive(Clone, Copy, Debug)]
+pub struct Collections {
+    pub field: Pubkey,
+    pub seed: Pubkey,
+    pub sapling: Pubkey,
+    pub ripe: Pubkey,
+}
+
+pub struct Farmer {
+    pub context: ProgramTestContext,
+    /// The ProgramAdminWallet, upgrade authority of FarmerHouse and authority of the farm
+    pub paw: Keypair,
+    pub user: Keypair,
+    pub farm: Farm,
+    pub collections: Collections,
+}
+
+fn test_program(name: &str) -> Vec<u8> {
+    read_file(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test-programs")
+            .join(name),
+    )
+}
+
+fn program_test(paw: &Pubkey, user: &Pubkey) -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    let rent = Rent::default();
+
+    for (program_id, name) in [
+        (mpl_token_metadata::ID, "mpl_token_metadata.so"),
+        (mpl_trifle::ID, "mpl_trifle.so"),
+    ] {
+        let data = test_program(name);
+        program_test.add_account(
+            program_id,
+            Account {
+                lamports: rent.minimum_balance(data.len()),
+                data,
+                owner: bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    let elf = read_file(
+        find_file("farmer_house.so")
+            .expect("farmer_house.so not found, run the tests with `cargo test-sbf`"),
+    );
+    let program_data_address = pda::find_program_data().0;
+    let mut program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*paw),
+    })
+    .unwrap();
+    program_data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+    program_data.extend_from_slice(&elf);
+    program_test.add_account(
+        program_data_address,
+        Account {
+            lamports: rent.minimum_balance(program_data.len()),
+            data: program_data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let program = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address: program_data_address,
+    })
+    .unwrap();
+    program_test.add_account(
+        farmer_house::ID,
+        Account {
+            lamports: rent.minimum_balance(program.len()),
+            data: program,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+
+    for (wallet, sol) in [(paw, 100), (user, 10)] {
+        program_test.add_account(
+            *wallet,
+            Account::new(sol * LAMPORTS_PER_SOL, 0, &system_program::id()),
+        );
+    }
+
+    program_test
+}
+
+impl Farmer {
+    /// Starts a bank where the PAW has minted the CurrencyToken and the collections, and the user holds
+    /// `USER_CURRENCY`. The farm itself is not initialized yet.
+    pub async fn start() -> Self {
+        let paw = Keypair::new();
+        let user = Keypair::new();
+        let context = program_test(&paw.pubkey(), &user.pubkey())
+            .start_with_context()
+            .await;
+
+        let mut farmer = Self {
+            context,
+            farm: Farm::new(0, paw.pubkey(), Pubkey::default(), CONSTRAINT_MODEL_NAME),
+            paw,
+            user,
+            collections: Collections {
+                field: Pubkey::default(),
+                seed: Pubkey::default(),
+                sapling: Pubkey::default(),
+                ripe: Pubkey::default(),
+            },
+        };
+
+        let currency_mint = farmer.create_mint(6).await;
+        let paw_wallet = farmer.paw.pubkey();
+        let user_wallet = farmer.user.pubkey();
+        farmer
+            .mint_tokens(&currency_mint, &paw_wallet, PAW_CURRENCY)
+            .await;
+        farmer
+            .transfer_tokens(&currency_mint, &user_wallet, USER_CURRENCY)
+            .await;
+        farmer.farm.spl_mint = currency_mint;
+
+        farmer.collections = Collections {
+            field: farmer.mint_collection("Field Collection", "FIELDCO").await,
+            seed: farmer
+                .mint_collection("Tomato Seed Collection", "TOSECO")
+                .await,
+            sapling: farmer
+                .mint_collection("Young Tomato Plant Collection", "TOPLCO")
+                .await,
+            ripe: farmer
+                .mint_collection("Ripe Tomato Collection", "RITOCO")
+                .await,
+        };
+
+        farmer
+    }
+
+    /// Starts a bank holding the 201-byte farms PDA of the v1 program at its legacy address, as
+    /// `initialize_farms_pda` created it before farms had instance ids
+    pub async fn start_v1() -> Self {
+        let mut farmer = Self::start().await;
+        farmer.farm = Farm::legacy(
+            farmer.paw.pubkey(),
+            farmer.farm.spl_mint,
+            CONSTRAINT_MODEL_NAME,
+        );
+        let (address, bump) = pda::find_legacy_farms_pda();
+        let mut data = FarmsPda::DISCRIMINATOR.to_vec();
+        data.push(bump);
+        for key in [
+            farmer.farm.authority,
+            farmer.farm.spl_mint,
+            farmer.collections.seed,
+            farmer.collections.sapling,
+            farmer.collections.ripe,
+            farmer.collections.field,
+        ] {
+            data.extend_from_slice(key.as_ref());
+        }
+        assert_eq!(data.len(), FarmsPda::V1_SPACE);
+        farmer.context.set_account(
+            &address,
+            &Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: farmer_house::ID,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+        farmer
+    }
+
+    /// Starts a bank with the farm initialized, its shop funded and its constraint model created
+    pub async fn open_shop() -> Self {
+        let mut farmer = Self::start().await;
+        farmer
+            .process_as_paw(&[farmer.farm.initialize_farms_pda(
+                farmer.collections.seed,
+                farmer.collections.sapling,
+                farmer.collections.ripe,
+                farmer.collections.field,
+            )])
+            .await
+            .unwrap();
+        let farms_pda = farmer.farm.farms_pda();
+        let currency_mint = farmer.farm.spl_mint;
+        farmer
+            .transfer_tokens(&currency_mint, &farms_pda, SHOP_CURRENCY)
+            .await;
+        farmer
+            .process_as_paw(&[farmer.farm.initialize_constraint_model(SCHEMA_URI)])
+            .await
+            .unwrap();
+        farmer
+    }
+
+    /// Opens the shop, and has the user buy a field and a tomato seed and plant it in `a1`.
+    /// Returns the field and seed mints.
+    pub async fn plant() -> (Self, Pubkey, Pubkey) {
+        let mut farmer = Self::open_shop().await;
+        let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+        farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+        (farmer, field_mint, seed_mint)
+    }
+
+    /// Opens the shop with the events, and has the user plant a tomato seed committed to them in `a1`
+    /// and water it until ripe. Returns the field and crop mints.
+    pub async fn grow_with_events(config: EventConfig) -> (Self, Pubkey, Pubkey) {
+        let mut farmer = Self::open_shop().await;
+        farmer
+            .process_as_paw(&[farmer.farm.set_events(config)])
+            .await
+            .unwrap();
+        let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+        farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+        farmer.water(&field_mint, &seed_mint).await.unwrap();
+        farmer.water(&field_mint, &seed_mint).await.unwrap();
+        (farmer, field_mint, seed_mint)
+    }
+
+    /// Opens the shop, and has the user buy a field and offer it for lease on `terms`. The PAW takes the
+    /// lease and buys a tomato seed to plant in it. Returns the field and seed mints.
+    pub async fn lease(terms: LeaseTerms) -> (Self, Pubkey, Pubkey) {
+        let mut farmer = Self::open_shop().await;
+        let field_mint = farmer.stock_field().await;
+        let seed_mint = farmer.stock_seed().await;
+        let user = farmer.user.pubkey();
+        let paw = farmer.paw.pubkey();
+        farmer
+            .process_as_user(&[
+                farmer.farm.buy_field(user, field_mint),
+                farmer.farm.offer_lease(user, field_mint, terms),
+            ])
+            .await
+            .unwrap();
+        farmer
+            .process_as_paw(&[
+                farmer.farm.take_lease(paw, field_mint, user),
+                farmer.farm.buy_seed(paw, seed_mint, None),
+            ])
+            .await
+            .unwrap();
+        (farmer, field_mint, seed_mint)
+    }
+
+    /// Opens the shop, and has the user buy a field and delegate `actions` on it to the PAW for
+    /// `FARMHAND_DURATION`. The PAW buys a tomato seed to plant in it. Returns the field and seed mints.
+    pub async fn delegate(actions: u8) -> (Self, Pubkey, Pubkey) {
+        let mut farmer = Self::open_shop().await;
+        let field_mint = farmer.stock_field().await;
+        let seed_mint = farmer.stock_seed().await;
+        let user = farmer.user.pubkey();
+        let paw = farmer.paw.pubkey();
+        let expires_at = farmer.now().await + FARMHAND_DURATION;
+        farmer
+            .process_as_user(&[
+                farmer.farm.buy_field(user, field_mint),
+                farmer
+                    .farm
+                    .delegate_farmhand(user, field_mint, paw, actions, expires_at),
+            ])
+            .await
+            .unwrap();
+        farmer
+            .process_as_paw(&[farmer.farm.buy_seed(paw, seed_mint, None)])
+            .await
+            .unwrap();
+        (farmer, field_mint, seed_mint)
+    }
+
+    /// Opens the shop, and has the user buy a field and found a co-op on it with `COOP_SHARES.0`
+    /// shares, the PAW joining it with `COOP_SHARES.1` when `with_paw`. The PAW buys a tomato seed to
+    /// plant in it. Returns the field and seed mints.
+    pub async fn coop(with_paw: bool) -> (Self, Pubkey, Pubkey) {
+        let mut farmer = Self::open_shop().await;
+        let field_mint = farmer.stock_field().await;
+        let seed_mint = farmer.stock_seed().await;
+        let user = farmer.user.pubkey();
+        let paw = farmer.paw.pubkey();
+        let mut members = vec![CoopMember {
+            wallet: user,
+            shares: COOP_SHARES.0,
+        }];
+        if with_paw {
+            members.push(CoopMember {
+                wallet: paw,
+                shares: COOP_SHARES.1,
+            });
+        }
+        farmer
+            .process_as_user(&[
+                farmer.farm.buy_field(user, field_mint),
+                farmer.farm.found_coop(user, field_mint, members),
+            ])
+            .await
+            .unwrap();
+        farmer
+            .process_as_paw(&[farmer.farm.buy_seed(paw, seed_mint, None)])
+            .await
+            .unwrap();
+        (farmer, field_mint, seed_mint)
+    }
+
+    pub async fn buy_field_and_seed(&mut self) -> (Pubkey, Pubkey) {
+        let field_mint = self.stock_field().await;
+        let seed_mint = self.stock_seed().await;
+        let user = self.user.pubkey();
+        self.process_as_user(&[
+            self.farm.buy_field(user, field_mint),
+            self.farm.buy_seed(user, seed_mint, None),
+        ])
+        .await
+        .unwrap();
+        (field_mint, seed_mint)
+    }
+
+    /// Plants the seed, then unverifies its collection like the TypeScript tests do, so watering can
+    /// move the crop into the next collection
+    pub async fn plant_in(&mut self, field_mint: &Pubkey, seed_mint: &Pubkey, crop_slot: &str) {
+        let user = self.user.pubkey();
+        self.process_as_user(&[self.farm.plant_seed(
+            user,
+            *field_mint,
+            *seed_mint,
+            crop_slot,
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+        self.unverify_collection(seed_mint, &self.collections.seed.clone())
+            .await;
+    }
+
+    /// Waters the tomato in `a1`, into the stage after the collection it's in
+    pub async fn water(
+        &mut self,
+        field_mint: &Pubkey,
+        crop_mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let user = self.user.pubkey();
+        let collection = self.metadata(crop_mint).await.collection.unwrap().key;
+        let next_stage = if collection == self.collections.seed {
+            CropStage::Sapling
+        } else {
+            CropStage::Ripe
+        };
+        let instruction = self.farm.water(
+            user,
+            *field_mint,
+            *crop_mint,
+            "a1",
+            next_stage,
+            CropOptions::default(),
+        );
+        self.process_as_user(&[instruction]).await
+    }
+
+    pub async fn stock_field(&mut self) -> Pubkey {
+        let collection = self.collections.field;
+        self.mint_farm_nft("Basic Field", "FIELD", collection).await
+    }
+
+    pub async fn stock_seed(&mut self) -> Pubkey {
+        let collection = self.collections.seed;
+        self.mint_farm_nft("Tomato Seed", "TOMATO", collection)
+            .await
+    }
+
+    /// Creates a fertilizer mint, registers it and gives the user `amount` fertilizer
+    pub async fn stock_fertilizer(&mut self, amount: u64) -> Pubkey {
+        let fertilizer_mint = self.create_mint(0).await;
+        self.process_as_paw(&[self.farm.initialize_fertilizer(fertilizer_mint)])
+            .await
+            .unwrap();
+        let user = self.user.pubkey();
+        self.mint_tokens(&fertilizer_mint, &user, amount).await;
+        fertilizer_mint
+    }
+
+    /// Registers recipe `0`, turning 3 and 1 of two new mints into 2 of its output, and gives the
+    /// user `batches` worth of its inputs
+    pub async fn stock_recipe(&mut self, processing_time: i64, batches: u64) -> Vec<Pubkey> {
+        let input_mints = vec![self.create_mint(0).await, self.create_mint(0).await];
+        let config = RecipeConfig {
+            inputs: vec![
+                RecipeInput {
+                    mint: input_mints[0],
+                    amount: 3,
+                    collection: false,
+                },
+                RecipeInput {
+                    mint: input_mints[1],
+                    amount: 1,
+                    collection: false,
+                },
+            ],
+            output_amount: 2,
+            processing_time,
+            output_collection: None,
+        };
+        self.process_as_paw(&[self.farm.set_recipe(0, config.clone())])
+            .await
+            .unwrap();
+        let user = self.user.pubkey();
+        for input in config.inputs {
+            self.mint_tokens(&input.mint, &user, input.amount * batches)
+                .await;
+        }
+        input_mints
+    }
+
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut with_budget = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
+        with_budget.extend_from_slice(instructions);
+        // Identical transactions in a row need a new blockhash, or the second one is dropped as a duplicate
+        let blockhash = self
+            .context
+            .banks_client
+            .get_new_latest_blockhash(&self.context.last_blockhash)
+            .await
+            .unwrap();
+        self.context.last_blockhash = blockhash;
+        let transaction = Transaction::new_signed_with_payer(
+            &with_budget,
+            Some(&signers[0].pubkey()),
+            &signers.to_vec(),
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    pub async fn process_as_paw(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<(), BanksClientError> {
+        let paw = clone_keypair(&self.paw);
+        self.process(instructions, &[&paw]).await
+    }
+
+    pub async fn process_as_user(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<(), BanksClientError> {
+        let user = clone_keypair(&self.user);
+        self.process(instructions, &[&user]).await
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// Moves the clock forward, to let watering windows and wither deadlines pass
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Moves past `slots` slots, each leaving its hash in the SlotHashes sysvar
+    pub async fn advance_slots(&mut self, slots: u64) {
+        let slot = self.context.banks_client.get_root_slot().await.unwrap();
+        for slot in slot + 1..=slot + slots {
+            self.context.warp_to_slot(slot).unwrap();
+        }
+    }
+
+    pub async fn account_data(&mut self, address: &Pubkey) -> Option<Vec<u8>> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .map(|account| account.data)
+    }
+
+    pub async fn program_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let data = self.account_data(address).await.expect("account not found");
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    pub async fn token_balance(&mut self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        match self
+            .account_data(&get_associated_token_address(owner, mint))
+            .await
+        {
+            Some(data) => TokenAccount::unpack(&data).unwrap().amount,
+            None => 0,
+        }
+    }
+
+    pub async fn metadata(&mut self, mint: &Pubkey) -> Metadata {
+        let data = self
+            .account_data(&pda::find_metadata(mint).0)
+            .await
+            .expect("metadata not found");
+        Metadata::safe_deserialize(&data).unwrap()
+    }
+
+    /// Fetches the accounts of the field and its crops, and decodes them with the `view` module
+    pub async fn field_view(&mut self, field_mint: &Pubkey) -> view::FieldView {
+        let farms_pda = self.farm.farms_pda();
+        let farms_pda_info = self.program_account::<FarmsPda>(&farms_pda).await;
+        let trifle_data = self
+            .account_data(&pda::find_trifle(field_mint, &farms_pda).0)
+            .await
+            .expect("trifle not found");
+
+        let mut crop_metadata = HashMap::new();
+        let mut crop_states = HashMap::new();
+        for (_, crop_mint) in view::planted_crops(&trifle_data).unwrap() {
+            let metadata = self.account_data(&pda::find_metadata(&crop_mint).0).await;
+            crop_metadata.insert(crop_mint, metadata.expect("metadata not found"));
+            let crop_state = self
+                .program_account::<CropState>(&pda::find_crop_state_pda(&farms_pda, &crop_mint).0)
+                .await;
+            crop_states.insert(crop_mint, crop_state);
+        }
+
+        view::field_view(
+            &farms_pda_info,
+            *field_mint,
+            &trifle_data,
+            &crop_metadata,
+            &crop_states,
+            view::SpeciesAccounts::default(),
+        )
+        .unwrap()
+    }
+
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let paw = self.paw.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &paw,
+                    &mint.pubkey(),
+                    rent.minimum_balance(Mint::LEN),
+                    Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    &paw,
+                    Some(&paw),
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&clone_keypair(&self.paw), &mint],
+        )
+        .await
+        .unwrap();
+        mint.pubkey()
+    }
+
+    /// Mints tokens to the ATA of `owner`, creating it if needed
+    pub async fn mint_tokens(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let paw = self.paw.pubkey();
+        let ata = get_associated_token_address(owner, mint);
+        let mut instructions = vec![];
+        if self.account_data(&ata).await.is_none() {
+            instructions.push(create_associated_token_account(
+                &paw,
+                owner,
+                mint,
+                &spl_token::id(),
+            ));
+        }
+        instructions.push(
+            spl_token::instruction::mint_to(&spl_token::id(), mint, &ata, &paw, &[], amount)
+                .unwrap(),
+        );
+        self.process_as_paw(&instructions).await.unwrap();
+    }
+
+    /// Transfers tokens from the PAW to the ATA of `owner`, creating it if needed
+    pub async fn transfer_tokens(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let paw = self.paw.pubkey();
+        let ata = get_associated_token_address(owner, mint);
+        let mut instructions = vec![];
+        if self.account_data(&ata).await.is_none() {
+            instructions.push(create_associated_token_account(
+                &paw,
+                owner,
+                mint,
+                &spl_token::id(),
+            ));
+        }
+        instructions.push(
+            spl_token::instruction::transfer(
+                &spl_token::id(),
+                &get_associated_token_address(&paw, mint),
+                &ata,
+                &paw,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        );
+        self.process_as_paw(&instructions).await.unwrap();
+    }
+
+    async fn mint_nft(
+        &mut self,
+        owner: &Pubkey,
+        name: &str,
+        symbol: &str,
+        collection: Option<Pubkey>,
+        collection_details: Option<CollectionDetails>,
+    ) -> Pubkey {
+        let paw = self.paw.pubkey();
+        let mint = self.create_mint(0).await;
+        self.mint_tokens(&mint, owner, 1).await;
+
+        let metadata = pda::find_metadata(&mint).0;
+        let mut instructions = vec![
+            create_metadata_accounts_v3(
+                mpl_token_metadata::ID,
+                metadata,
+                mint,
+                paw,
+                paw,
+                paw,
+                name.to_string(),
+                symbol.to_string(),
+                METADATA_URI.to_string(),
+                Some(vec![Creator {
+                    address: paw,
+                    verified: true,
+                    share: 100,
+                }]),
+                0,
+                true,
+                true,
+                collection.map(|key| Collection {
+                    verified: false,
+                    key,
+                }),
+                None,
+                collection_details,
+            ),
+            create_master_edition_v3(
+                mpl_token_metadata::ID,
+                pda::find_master_edition(&mint).0,
+                mint,
+                paw,
+                paw,
+                metadata,
+                paw,
+                Some(0),
+            ),
+        ];
+        if let Some(collection) = collection {
+            instructions.push(verify_sized_collection_item(
+                mpl_token_metadata::ID,
+                metadata,
+                paw,
+                paw,
+                collection,
+                pda::find_metadata(&collection).0,
+                pda::find_master_edition(&collection).0,
+                None,
+            ));
+        }
+        self.process_as_paw(&instructions).await.unwrap();
+        mint
+    }
+
+    pub async fn mint_collection(&mut self, name: &str, symbol: &str) -> Pubkey {
+        let paw = self.paw.pubkey();
+        self.mint_nft(
+            &paw,
+            name,
+            symbol,
+            None,
+            Some(CollectionDetails::V1 { size: 0 }),
+        )
+        .await
+    }
+
+    /// Mints an NFT of `collection` into the shop, with the farms PDA as its update authority
+    pub async fn mint_farm_nft(&mut self, name: &str, symbol: &str, collection: Pubkey) -> Pubkey {
+        let farms_pda = self.farm.farms_pda();
+        let mint = self
+            .mint_nft(&farms_pda, name, symbol, Some(collection), None)
+            .await;
+        let paw = self.paw.pubkey();
+        self.process_as_paw(&[update_metadata_accounts_v2(
+            mpl_token_metadata::ID,
+            pda::find_metadata(&mint).0,
+            paw,
+            Some(farms_pda),
+            None,
+            None,
+            None,
+        )])
+        .await
+        .unwrap();
+        mint
+    }
+
+    pub async fn unverify_collection(&mut self, mint: &Pubkey, collection: &Pubkey) {
+        let paw = self.paw.pubkey();
+        self.process_as_paw(&[unverify_sized_collection_item(
+            mpl_token_metadata::ID,
+            pda::find_metadata(mint).0,
+            paw,
+            paw,
+            *collection,
+            pda::find_metadata(collection).0,
+            pda::find_master_edition(collection).0,
+            None,
+        )])
+        .await
+        .unwrap();
+    }
+}
+
+/// Signing with a keypair of the `Farmer` while borrowing it mutably needs a copy
+/// The fungible inputs of a recipe, crafted from the user's tokens of `mints`
+pub fn mint_inputs(mints: &[Pubkey]) -> Vec<CraftInput> {
+    mints.iter().copied().map(CraftInput::Mint).collect()
+}
+
+pub fn clone_keypair(keypair: &Keypair) -> Keypair {
+    Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+}
+
+/// Points every account of `instruction` at `address` to `replacement` instead
+pub fn with_account(
+    mut instruction: Instruction,
+    address: &Pubkey,
+    replacement: &Pubkey,
+) -> Instruction {
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == *address {
+            account.pubkey = *replacement;
+        }
+    }
+    instruction
+}
+
+pub fn assert_farmer_house_error(result: Result<(), BanksClientError>, error: FarmerHouseError) {
+    let expected = u32::from(error);
+    match result
+        .expect_err("the transaction should have failed")
+        .unwrap()
+    {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(
+                code, expected,
+                "expected {error:?}, got custom error {code}"
+            )
+        }
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
@@ -0,0 +1,938 @@
+//! One failing transaction for each error of `FarmerHouseError` a client can run into.
+//!
+//! `MissingRequiredSignature` is left out, the authority is a `Signer` and Anchor rejects a missing
+//! signature before the instruction runs. `CollectionNotVerified` and `CropNotRipe` are not returned
+//! by any instruction.
+
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    farmer_house::errors::FarmerHouseError,
+    solana_sdk::{signature::Signer, system_program},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+#[tokio::test]
+async fn owner_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let fertilizer_mint = farmer.stock_fertilizer(1).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    // The PAW signs for a field the user holds
+    let instruction = with_account(
+        farmer.farm.fertilize(
+            user,
+            field_mint,
+            seed_mint,
+            fertilizer_mint,
+            "a1",
+            CropOptions::default(),
+        ),
+        &user,
+        &paw,
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::OwnerMismatch);
+}
+
+#[tokio::test]
+async fn program_mismatch() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+
+    let instruction = with_account(
+        farmer.farm.buy_field(user, field_mint),
+        &mpl_trifle::ID,
+        &system_program::id(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::ProgramMismatch);
+}
+
+#[tokio::test]
+async fn authority_mismatch() {
+    let mut farmer = Farmer::start().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    // Only the upgrade authority of the program can initialize a farm
+    let instruction = with_account(
+        farmer.farm.initialize_farms_pda(
+            farmer.collections.seed,
+            farmer.collections.sapling,
+            farmer.collections.ripe,
+            farmer.collections.field,
+        ),
+        &paw,
+        &user,
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::AuthorityMismatch);
+}
+
+#[tokio::test]
+async fn mint_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+    let currency_mint = farmer.farm.spl_mint;
+
+    // No fertilizer has been registered, so nothing can be used as one
+    let instruction = farmer.farm.fertilize(
+        user,
+        field_mint,
+        seed_mint,
+        currency_mint,
+        "a1",
+        CropOptions::default(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::MintMismatch);
+}
+
+#[tokio::test]
+async fn amount_mismatch() {
+    let mut farmer = Farmer::open_shop().await;
+    let paw = farmer.paw.pubkey();
+
+    let instruction = farmer.farm.withdraw_treasury(paw, SHOP_CURRENCY + 1);
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::AmountMismatch);
+}
+
+#[tokio::test]
+async fn collection_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+
+    let instruction =
+        farmer
+            .farm
+            .harvest_crop(user, field_mint, seed_mint, "a1", CropOptions::default());
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CollectionMismatch);
+}
+
+#[tokio::test]
+async fn trifle_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let fertilizer_mint = farmer.stock_fertilizer(1).await;
+    let user = farmer.user.pubkey();
+
+    // The crop is planted in a1, b1 is empty
+    let instruction = farmer.farm.fertilize(
+        user,
+        field_mint,
+        seed_mint,
+        fertilizer_mint,
+        "b1",
+        CropOptions::default(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::TrifleMismatch);
+}
+
+#[tokio::test]
+async fn crop_ready() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+
+    let result = farmer.water(&field_mint, &seed_mint).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropReady);
+
+    // Nor fertilized
+    let fertilizer_mint = farmer.stock_fertilizer(1).await;
+    let user = farmer.user.pubkey();
+    let instruction = farmer.farm.fertilize(
+        user,
+        field_mint,
+        seed_mint,
+        fertilizer_mint,
+        "a1",
+        CropOptions::default(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropReady);
+}
+
+#[tokio::test]
+async fn invalid_growth_config() {
+    let mut farmer = Farmer::open_shop().await;
+
+    let instruction = farmer.farm.update_growth_config(GrowthConfig {
+        watering_window: 0,
+        ..GrowthConfig::default()
+    });
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidGrowthConfig);
+
+    // Crops would wither before they could be watered
+    let instruction = farmer.farm.update_growth_config(GrowthConfig {
+        stage_growth_time: GrowthConfig::default().wither_deadline + 1,
+        ..GrowthConfig::default()
+    });
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidGrowthConfig);
+}
+
+#[tokio::test]
+async fn crop_withered() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    farmer
+        .process_as_paw(&[farmer.farm.update_growth_config(GrowthConfig {
+            watering_window: 1,
+            wither_deadline: 2,
+            ..GrowthConfig::default()
+        })])
+        .await
+        .unwrap();
+    farmer.advance_clock(10).await;
+
+    let result = farmer.water(&field_mint, &seed_mint).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropWithered);
+}
+
+#[tokio::test]
+async fn crop_not_withered() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+
+    let instruction =
+        farmer
+            .farm
+            .clear_slot(user, field_mint, seed_mint, "a1", CropOptions::default());
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropNotWithered);
+}
+
+#[tokio::test]
+async fn crop_growing() {
+    let mut farmer = Farmer::open_shop().await;
+    farmer
+        .process_as_paw(&[farmer.farm.update_growth_config(GrowthConfig {
+            stage_growth_time: 60 * 60,
+            ..GrowthConfig::default()
+        })])
+        .await
+        .unwrap();
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+
+    let result = farmer.water(&field_mint, &seed_mint).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropGrowing);
+}
+
+#[tokio::test]
+async fn crop_not_growing() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let fertilizer_mint = farmer.stock_fertilizer(1).await;
+    let user = farmer.user.pubkey();
+
+    // Crops are ready to be watered as soon as they're planted with the default growth config
+    let instruction = farmer.farm.fertilize(
+        user,
+        field_mint,
+        seed_mint,
+        fertilizer_mint,
+        "a1",
+        CropOptions::default(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropNotGrowing);
+}
+
+#[tokio::test]
+async fn invalid_species() {
+    let mut farmer = Farmer::open_shop().await;
+
+    // The tomato is built into the farms PDA and can't be registered
+    let instruction = farmer.farm.register_species(
+        0,
+        farmer.collections.seed,
+        farmer.collections.sapling,
+        farmer.collections.ripe,
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidSpecies);
+}
+
+#[tokio::test]
+async fn invalid_farms_pda() {
+    let mut farmer = Farmer::open_shop().await;
+    farmer
+        .process_as_paw(&[farmer.farm.register_species(
+            1,
+            farmer.collections.seed,
+            farmer.collections.sapling,
+            farmer.collections.ripe,
+        )])
+        .await
+        .unwrap();
+    let farms_pda = farmer.farm.farms_pda();
+    let species = pda::find_species_pda(&farms_pda, 1).0;
+
+    let instruction = with_account(farmer.farm.migrate_farms_pda(), &farms_pda, &species);
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidFarmsPda);
+}
+
+#[tokio::test]
+async fn farms_pda_up_to_date() {
+    let mut farmer = Farmer::open_shop().await;
+
+    let instruction = farmer.farm.migrate_farms_pda();
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::FarmsPdaUpToDate);
+}
+
+#[tokio::test]
+async fn invalid_stage_metadata() {
+    let mut farmer = Farmer::open_shop().await;
+
+    // Crops are planted as seeds, there is nothing to apply to that stage
+    let instruction = farmer.farm.set_stage_metadata(
+        0,
+        CropStage::Seed,
+        StageMetadataData {
+            name: "Tomato Seed".to_string(),
+            symbol: "TOMATO".to_string(),
+            uri: METADATA_URI.to_string(),
+            seller_fee_basis_points: 500,
+            creators: None,
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidStageMetadata);
+
+    // Without the PAW as first creator, the crop could no longer be planted in a slot
+    let instruction = farmer.farm.set_stage_metadata(
+        0,
+        CropStage::Sapling,
+        StageMetadataData {
+            name: "Tomato Sapling".to_string(),
+            symbol: "TOMATO".to_string(),
+            uri: METADATA_URI.to_string(),
+            seller_fee_basis_points: 500,
+            creators: Some(vec![StageCreator {
+                address: farmer.user.pubkey(),
+                share: 100,
+            }]),
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidStageMetadata);
+
+    // Watering passes the metadata of the stage the crop grows to, set or not
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    let user = farmer.user.pubkey();
+    let instruction = farmer.farm.water(
+        user,
+        field_mint,
+        seed_mint,
+        "a1",
+        CropStage::Ripe,
+        CropOptions::default(),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidStageMetadata);
+}
+
+#[tokio::test]
+async fn invalid_crop_slot() {
+    let mut farmer = Farmer::open_shop().await;
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    let user = farmer.user.pubkey();
+
+    let instruction =
+        farmer
+            .farm
+            .plant_seed(user, field_mint, seed_mint, "z9", CropOptions::default());
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidCropSlot);
+}
+
+#[tokio::test]
+async fn invalid_produce_config() {
+    let mut farmer = Farmer::open_shop().await;
+
+    let instruction = farmer.farm.set_produce(
+        0,
+        ProduceConfig {
+            bronze_yield: 0,
+            silver_yield: 1,
+            gold_yield: 2,
+            unit_price: 1_000_000,
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidProduceConfig);
+}
+
+#[tokio::test]
+async fn produce_mismatch() {
+    let mut farmer = Farmer::open_shop().await;
+    farmer
+        .process_as_paw(&[farmer.farm.set_produce(0, PRODUCE_CONFIG)])
+        .await
+        .unwrap();
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let produce_mint = pda::find_produce_mint(&farms_pda, 0).0;
+
+    // The user tries to sell their currency tokens as produce
+    let instruction = with_account(
+        farmer.farm.sell_produce(user, 0, 1),
+        &get_associated_token_address(&user, &produce_mint),
+        &get_associated_token_address(&user, &farmer.farm.spl_mint),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::ProduceMismatch);
+
+    // Once its species has produce, a ripe tomato can't be harvested as a crop to sell
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    let instruction =
+        farmer
+            .farm
+            .harvest_crop(user, field_mint, seed_mint, "a1", CropOptions::default());
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::ProduceMismatch);
+}
+
+#[tokio::test]
+async fn invalid_recipe() {
+    let mut farmer = Farmer::open_shop().await;
+    let input_mint = farmer.create_mint(0).await;
+    let input = RecipeInput {
+        mint: input_mint,
+        amount: 1,
+        collection: false,
+    };
+
+    // The same mint can't be listed twice
+    let instruction = farmer.farm.set_recipe(
+        0,
+        RecipeConfig {
+            inputs: vec![input, input],
+            output_amount: 1,
+            processing_time: 0,
+            output_collection: None,
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidRecipe);
+
+    // Nor can a recipe burn the collection it hands out
+    let collection = farmer.collections.ripe;
+    let instruction = farmer.farm.set_recipe(
+        0,
+        RecipeConfig {
+            inputs: vec![RecipeInput {
+                mint: collection,
+                amount: 2,
+                collection: true,
+            }],
+            output_amount: 1,
+            processing_time: 0,
+            output_collection: Some(collection),
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidRecipe);
+}
+
+#[tokio::test]
+async fn recipe_mismatch() {
+    let mut farmer = Farmer::open_shop().await;
+    let input_mints = farmer.stock_recipe(0, 1).await;
+    let user = farmer.user.pubkey();
+
+    // The inputs are passed in the wrong order
+    let instruction = farmer.farm.craft(
+        user,
+        0,
+        &mint_inputs(&[input_mints[1], input_mints[0]]),
+        &[],
+        false,
+        1,
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::RecipeMismatch);
+}
+
+#[tokio::test]
+async fn craft_in_progress() {
+    let mut farmer = Farmer::open_shop().await;
+    let input_mints = farmer.stock_recipe(60, 2).await;
+    let user = farmer.user.pubkey();
+    farmer
+        .process_as_user(&[farmer
+            .farm
+            .craft(user, 0, &mint_inputs(&input_mints), &[], true, 1)])
+        .await
+        .unwrap();
+
+    let instruction = farmer
+        .farm
+        .craft(user, 0, &mint_inputs(&input_mints), &[], true, 1);
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CraftInProgress);
+}
+
+#[tokio::test]
+async fn craft_processing() {
+    let mut farmer = Farmer::open_shop().await;
+    let input_mints = farmer.stock_recipe(60, 1).await;
+    let user = farmer.user.pubkey();
+    farmer
+        .process_as_user(&[farmer
+            .farm
+            .craft(user, 0, &mint_inputs(&input_mints), &[], true, 1)])
+        .await
+        .unwrap();
+
+    let instruction = farmer.farm.collect_craft(user, 0, &[]);
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CraftProcessing);
+}
+
+#[tokio::test]
+async fn invalid_companions() {
+    let mut farmer = Farmer::open_shop().await;
+
+    // A neighbour can at most remove all of the growth time
+    let instruction = farmer.farm.set_companions(
+        0,
+        vec![CompanionEffect {
+            neighbour_species_id: 0,
+            growth_time_bps: -20000,
+            yield_bonus: 0,
+        }],
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidCompanions);
+}
+
+#[tokio::test]
+async fn invalid_event_config() {
+    let mut farmer = Farmer::open_shop().await;
+
+    // Bumper harvests and pests together can't take more than every roll
+    let instruction = farmer.farm.set_events(EventConfig {
+        reveal_delay: 1,
+        bumper_bps: 6000,
+        pest_bps: 5000,
+    });
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidEventConfig);
+}
+
+#[tokio::test]
+async fn events_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::grow_with_events(EventConfig {
+        reveal_delay: 1,
+        bumper_bps: 0,
+        pest_bps: 0,
+    })
+    .await;
+    let user = farmer.user.pubkey();
+    farmer.advance_slots(2).await;
+
+    // The crop committed to a slot hash, so it can't be harvested without revealing it
+    let instruction =
+        farmer
+            .farm
+            .harvest_crop(user, field_mint, seed_mint, "a1", CropOptions::default());
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::EventsMismatch);
+
+    // A crop planted without events has no slot hash to reveal
+    let (mut farmer, _, seed_mint) = Farmer::plant().await;
+    let instruction = farmer.farm.reveal_crop(seed_mint);
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::EventsMismatch);
+}
+
+#[tokio::test]
+async fn crop_not_revealed() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::grow_with_events(EventConfig {
+        reveal_delay: 1000,
+        bumper_bps: 0,
+        pest_bps: 0,
+    })
+    .await;
+    let user = farmer.user.pubkey();
+
+    let instruction = farmer.farm.harvest_crop(
+        user,
+        field_mint,
+        seed_mint,
+        "a1",
+        CropOptions {
+            reveal: true,
+            ..Default::default()
+        },
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CropNotRevealed);
+}
+
+#[tokio::test]
+async fn invalid_mutation() {
+    let mut farmer = Farmer::open_shop().await;
+    let collection = farmer.collections.ripe;
+
+    let instruction = farmer.farm.set_mutation(
+        0,
+        MutationConfig {
+            collection,
+            chance_bps: 10001,
+            sell_price: 1,
+            max_supply: 1,
+            data: StageMetadataData {
+                name: "Golden Tomato".to_string(),
+                symbol: "GOLD".to_string(),
+                uri: String::new(),
+                seller_fee_basis_points: 0,
+                creators: None,
+            },
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidMutation);
+}
+
+#[tokio::test]
+async fn field_not_empty() {
+    let (mut farmer, field_mint, _) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+
+    // The seed is still growing in a1
+    let instruction = farmer.farm.sell_field(user, field_mint);
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::FieldNotEmpty);
+}
+
+#[tokio::test]
+async fn invalid_prices() {
+    let mut farmer = Farmer::open_shop().await;
+
+    let instruction = farmer.farm.update_prices(ShopPrices {
+        field: 0,
+        ..ShopPrices::default()
+    });
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidPrices);
+}
+
+#[tokio::test]
+async fn invalid_lease_terms() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+
+    let instruction = farmer.farm.offer_lease(
+        user,
+        field_mint,
+        LeaseTerms {
+            fee: 0,
+            duration: 3600,
+            share_bps: 10001,
+        },
+    );
+    let result = farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint), instruction])
+        .await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidLeaseTerms);
+}
+
+#[tokio::test]
+async fn lease_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::lease(LEASE_TERMS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let lease = pda::find_lease_pda(&farmer.farm.farms_pda(), &field_mint).0;
+
+    // The owner's wallet passed as the vault of the lease
+    let instruction = with_account(
+        farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                lessor: Some(user),
+                ..Default::default()
+            },
+        ),
+        &pda::find_lease_vault_pda(&lease).0,
+        &user,
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::LeaseMismatch);
+}
+
+#[tokio::test]
+async fn lease_expired() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::lease(LEASE_TERMS).await;
+    let paw = farmer.paw.pubkey();
+    farmer.advance_clock(LEASE_TERMS.duration).await;
+
+    let instruction = farmer.farm.plant_seed(
+        paw,
+        field_mint,
+        seed_mint,
+        "a1",
+        CropOptions {
+            lessor: Some(farmer.user.pubkey()),
+            ..Default::default()
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::LeaseExpired);
+}
+
+#[tokio::test]
+async fn lease_taken() {
+    let (mut farmer, field_mint, _) = Farmer::lease(LEASE_TERMS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    let instruction = farmer.farm.take_lease(paw, field_mint, user);
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::LeaseTaken);
+}
+
+#[tokio::test]
+async fn lease_active() {
+    let (mut farmer, field_mint, _) = Farmer::lease(LEASE_TERMS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    let instruction = farmer.farm.end_lease(user, field_mint, Some(paw));
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::LeaseActive);
+}
+
+#[tokio::test]
+async fn invalid_farmhand() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let expires_at = farmer.now().await + FARMHAND_DURATION;
+
+    let instruction = farmer
+        .farm
+        .delegate_farmhand(user, field_mint, paw, 0, expires_at);
+    let result = farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint), instruction])
+        .await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidFarmhand);
+}
+
+#[tokio::test]
+async fn farmhand_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::delegate(Farmhand::ALL_ACTIONS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let farmhand = pda::find_farmhand_pda(&farmer.farm.farms_pda(), &field_mint).0;
+
+    // The owner's wallet passed as the vault of the farmhand
+    let instruction = with_account(
+        farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        ),
+        &pda::find_farmhand_vault_pda(&farmhand).0,
+        &user,
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::FarmhandMismatch);
+}
+
+#[tokio::test]
+async fn farmhand_not_allowed() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::delegate(Farmhand::WATER).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    let instruction = farmer.farm.plant_seed(
+        paw,
+        field_mint,
+        seed_mint,
+        "a1",
+        CropOptions {
+            farmhand: Some(user),
+            ..Default::default()
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::FarmhandNotAllowed);
+}
+
+#[tokio::test]
+async fn invalid_coop() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    // The user founds a co-op they are not a member of
+    let instruction = farmer.farm.found_coop(
+        user,
+        field_mint,
+        vec![CoopMember {
+            wallet: paw,
+            shares: 1,
+        }],
+    );
+    let result = farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint), instruction])
+        .await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidCoop);
+}
+
+#[tokio::test]
+async fn coop_mismatch() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::coop(true).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let coop = pda::find_coop_pda(&farmer.farm.farms_pda(), &field_mint).0;
+
+    // The user's wallet passed as the vault of the co-op
+    let instruction = with_account(
+        farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        ),
+        &pda::find_coop_vault_pda(&coop).0,
+        &user,
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CoopMismatch);
+}
+
+#[tokio::test]
+async fn not_coop_member() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::coop(false).await;
+    let paw = farmer.paw.pubkey();
+
+    let instruction = farmer.farm.plant_seed(
+        paw,
+        field_mint,
+        seed_mint,
+        "a1",
+        CropOptions {
+            coop: true,
+            ..Default::default()
+        },
+    );
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::NotCoopMember);
+}
+
+#[tokio::test]
+async fn coop_quorum() {
+    let (mut farmer, field_mint, _) = Farmer::coop(true).await;
+    let paw = farmer.paw.pubkey();
+
+    // The PAW holds a quarter of the shares
+    let instruction = farmer.farm.dissolve_coop(paw, field_mint, &[]);
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CoopQuorum);
+}
+
+#[tokio::test]
+async fn invalid_listing() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+
+    let instruction = farmer.farm.create_listing(user, field_mint, 0, None);
+    let result = farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint), instruction])
+        .await;
+    assert_farmer_house_error(result, FarmerHouseError::InvalidListing);
+}
+
+#[tokio::test]
+async fn listing_mismatch() {
+    let mut farmer = Farmer::open_shop().await;
+    let field_mint = farmer.stock_field().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    farmer
+        .process_as_user(&[
+            farmer.farm.buy_field(user, field_mint),
+            farmer
+                .farm
+                .create_listing(user, field_mint, 40_000_000, None),
+        ])
+        .await
+        .unwrap();
+
+    // The PAW offers less than the listing asks
+    let instruction = farmer.farm.buy_listing(paw, field_mint, user, 30_000_000);
+    let result = farmer.process_as_paw(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::ListingMismatch);
+}
+
+#[tokio::test]
+async fn coop_crops_unsold() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::coop(true).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let options = CropOptions {
+        coop: true,
+        ..Default::default()
+    };
+    farmer
+        .process_as_paw(&[farmer
+            .farm
+            .plant_seed(paw, field_mint, seed_mint, "a1", options)])
+        .await
+        .unwrap();
+    farmer
+        .unverify_collection(&seed_mint, &farmer.collections.seed.clone())
+        .await;
+    for stage in [CropStage::Sapling, CropStage::Ripe] {
+        farmer
+            .process_as_paw(&[farmer
+                .farm
+                .water(paw, field_mint, seed_mint, "a1", stage, options)])
+            .await
+            .unwrap();
+    }
+    farmer
+        .process_as_paw(&[farmer
+            .farm
+            .harvest_crop(paw, field_mint, seed_mint, "a1", options)])
+        .await
+        .unwrap();
+
+    // The harvest is still in the vault of the co-op
+    let instruction = farmer.farm.dissolve_coop(user, field_mint, &[]);
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::CoopCropsUnsold);
+}
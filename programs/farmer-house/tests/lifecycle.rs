@@ -0,0 +1,1194 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    farmer_house::{errors::FarmerHouseError, FarmsPda},
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const TOMATO_URI: &str =
+    "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json";
+
+fn stage_data(name: &str) -> StageMetadataData {
+    StageMetadataData {
+        name: name.to_string(),
+        symbol: "TOMATO".to_string(),
+        uri: TOMATO_URI.to_string(),
+        seller_fee_basis_points: 500,
+        creators: None,
+    }
+}
+
+#[tokio::test]
+async fn grows_a_tomato_from_seed_to_sale() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let currency_mint = farmer.farm.spl_mint;
+
+    // Buying a field binds it to a Trifle, buying a seed hands it over as is
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 1);
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        USER_CURRENCY - 70_000_000 - 2_000_000
+    );
+
+    // Planting moves the seed into the escrow of the field
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    let trifle = pda::find_trifle(&field_mint, &farms_pda).0;
+    let escrow = pda::find_escrow(&field_mint, &trifle).0;
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&escrow, &seed_mint).await, 1);
+
+    farmer
+        .process_as_paw(&[
+            farmer
+                .farm
+                .set_stage_metadata(0, CropStage::Sapling, stage_data("Tomato Sapling")),
+            farmer
+                .farm
+                .set_stage_metadata(0, CropStage::Ripe, stage_data("Ripe Tomato")),
+        ])
+        .await
+        .unwrap();
+
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Sapling,
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+    let metadata = farmer.metadata(&seed_mint).await;
+    assert_eq!(metadata.data.name.trim_end_matches('\0'), "Tomato Sapling");
+    assert_eq!(metadata.data.seller_fee_basis_points, 500);
+    assert_eq!(metadata.collection.unwrap().key, farmer.collections.sapling);
+
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Ripe,
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+    let metadata = farmer.metadata(&seed_mint).await;
+    assert_eq!(metadata.data.name.trim_end_matches('\0'), "Ripe Tomato");
+    assert_eq!(metadata.collection.unwrap().key, farmer.collections.ripe);
+
+    // Until it is harvested and graded, the ripe tomato is worth the base ripe buyback
+    let field_view = farmer.field_view(&field_mint).await;
+    assert!(!field_view.tool_equipped);
+    assert_eq!(
+        field_view.slots,
+        vec![view::SlotView {
+            crop_slot: "a1".to_string(),
+            crop_mint: seed_mint,
+            species_id: 0,
+            stage: Some(CropStage::Ripe),
+            mutated: false,
+            sell_price: Some(7_000_000),
+        }]
+    );
+
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&escrow, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 1);
+
+    // Both waterings were on time, so the crop is gold and sells for 1.5x the ripe buyback
+    let crop_state = pda::find_crop_state_pda(&farms_pda, &seed_mint).0;
+    farmer
+        .process_as_user(&[farmer.farm.sell_crop(user, seed_mint, true, None, false)])
+        .await
+        .unwrap();
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        USER_CURRENCY - 70_000_000 - 2_000_000 + 10_500_000
+    );
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&farms_pda, &seed_mint).await, 1);
+    assert!(farmer.account_data(&crop_state).await.is_none());
+}
+
+#[tokio::test]
+async fn harvests_a_tomato_into_produce() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let currency_mint = farmer.farm.spl_mint;
+    let produce_mint = pda::find_produce_mint(&farms_pda, 0).0;
+
+    farmer
+        .process_as_paw(&[farmer.farm.set_produce(0, PRODUCE_CONFIG)])
+        .await
+        .unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+
+    // The gold tomato is burned for the gold yield, and its CropState goes with it
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                produce: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 0);
+    assert!(farmer
+        .account_data(&pda::find_metadata(&seed_mint).0)
+        .await
+        .is_none());
+    assert!(farmer
+        .account_data(&pda::find_crop_state_pda(&farms_pda, &seed_mint).0)
+        .await
+        .is_none());
+    assert_eq!(farmer.token_balance(&user, &produce_mint).await, 3);
+
+    // Passing their own produce account as the shop's, the user would keep the produce and be paid
+    let instruction = with_account(
+        farmer.farm.sell_produce(user, 0, 2),
+        &get_associated_token_address(&farms_pda, &produce_mint),
+        &get_associated_token_address(&user, &produce_mint),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::OwnerMismatch);
+
+    let balance_before = farmer.token_balance(&user, &currency_mint).await;
+    farmer
+        .process_as_user(&[farmer.farm.sell_produce(user, 0, 2)])
+        .await
+        .unwrap();
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        balance_before + 2 * 3_000_000
+    );
+    assert_eq!(farmer.token_balance(&user, &produce_mint).await, 1);
+    assert_eq!(farmer.token_balance(&farms_pda, &produce_mint).await, 2);
+}
+
+#[tokio::test]
+async fn pests_revealed_at_harvest_spoil_the_crop() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::grow_with_events(EventConfig {
+        reveal_delay: 2,
+        bumper_bps: 0,
+        pest_bps: 10000,
+    })
+    .await;
+    let user = farmer.user.pubkey();
+    let produce_mint = pda::find_produce_mint(&farmer.farm.farms_pda(), 0).0;
+    farmer
+        .process_as_paw(&[farmer.farm.set_produce(0, PRODUCE_CONFIG)])
+        .await
+        .unwrap();
+
+    // Once the committed slot has passed, its hash reveals pests and the gold tomato only yields
+    // like a bronze one
+    farmer.advance_slots(4).await;
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                produce: true,
+                reveal: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &produce_mint).await, 1);
+}
+
+#[tokio::test]
+async fn revealed_crops_harvest_after_the_slot_hash_is_gone() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::grow_with_events(EventConfig {
+        reveal_delay: 1,
+        bumper_bps: 0,
+        pest_bps: 10000,
+    })
+    .await;
+    let user = farmer.user.pubkey();
+    let produce_mint = pda::find_produce_mint(&farmer.farm.farms_pda(), 0).0;
+    farmer
+        .process_as_paw(&[farmer.farm.set_produce(0, PRODUCE_CONFIG)])
+        .await
+        .unwrap();
+
+    // Anyone can record the hash right after the committed slot, long before the harvest
+    farmer.advance_slots(2).await;
+    farmer
+        .process_as_paw(&[farmer.farm.reveal_crop(seed_mint)])
+        .await
+        .unwrap();
+
+    // The SlotHashes sysvar has long dropped the hash, but the harvest rolls the recorded one
+    farmer.advance_slots(600).await;
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                produce: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &produce_mint).await, 1);
+}
+
+#[tokio::test]
+async fn ripening_crops_mutate_until_the_supply_runs_out() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let currency_mint = farmer.farm.spl_mint;
+    let golden_collection = farmer.mint_collection("Golden Tomatoes", "GOLD").await;
+    farmer
+        .process_as_paw(&[
+            farmer.farm.set_events(EventConfig {
+                reveal_delay: 1,
+                bumper_bps: 0,
+                pest_bps: 0,
+            }),
+            farmer.farm.set_mutation(
+                0,
+                MutationConfig {
+                    collection: golden_collection,
+                    chance_bps: 10000,
+                    sell_price: 50_000_000,
+                    max_supply: 1,
+                    data: stage_data("Golden Tomato"),
+                },
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    let second_mint = farmer.stock_seed().await;
+    farmer
+        .process_as_user(&[farmer.farm.buy_seed(user, second_mint, None)])
+        .await
+        .unwrap();
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    farmer.plant_in(&field_mint, &second_mint, "b1").await;
+    farmer.advance_slots(2).await;
+
+    // Every ripening crop would mutate, but the supply only has room for the first one
+    for (crop_mint, crop_slot) in [(seed_mint, "a1"), (second_mint, "b1")] {
+        for next_stage in [CropStage::Sapling, CropStage::Ripe] {
+            farmer
+                .process_as_user(&[farmer.farm.water(
+                    user,
+                    field_mint,
+                    crop_mint,
+                    crop_slot,
+                    next_stage,
+                    CropOptions {
+                        reveal: true,
+                        mutation: true,
+                        ..Default::default()
+                    },
+                )])
+                .await
+                .unwrap();
+        }
+    }
+    let metadata = farmer.metadata(&seed_mint).await;
+    assert_eq!(metadata.data.name.trim_end_matches('\0'), "Golden Tomato");
+    assert_eq!(metadata.collection.unwrap().key, golden_collection);
+    let metadata = farmer.metadata(&second_mint).await;
+    assert_eq!(metadata.collection.unwrap().key, farmer.collections.ripe);
+
+    // The gold golden tomato sells for 1.5x the price of its mutation
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                reveal: true,
+                mutation: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    let balance_before = farmer.token_balance(&user, &currency_mint).await;
+    farmer
+        .process_as_user(&[farmer.farm.sell_crop(user, seed_mint, true, None, true)])
+        .await
+        .unwrap();
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        balance_before + 75_000_000
+    );
+}
+
+#[tokio::test]
+async fn sells_an_empty_field_back_to_the_shop() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let currency_mint = farmer.farm.spl_mint;
+    let field_mint = farmer.stock_field().await;
+    farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint)])
+        .await
+        .unwrap();
+
+    // The field goes back into the shop's inventory for half of what it cost
+    farmer
+        .process_as_user(&[farmer.farm.sell_field(user, field_mint)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 0);
+    assert_eq!(farmer.token_balance(&farms_pda, &field_mint).await, 1);
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        USER_CURRENCY - 70_000_000 + 35_000_000
+    );
+
+    // Its Trifle stays bound to it, ready for the next buyer
+    farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+}
+
+#[tokio::test]
+async fn trades_a_field_between_players_on_the_market() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let currency_mint = farmer.farm.spl_mint;
+    let field_mint = farmer.stock_field().await;
+    let listing = pda::find_listing_pda(&farms_pda, &field_mint).0;
+    farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint)])
+        .await
+        .unwrap();
+
+    // The listing escrows the field until the seller cancels it
+    farmer
+        .process_as_user(&[farmer
+            .farm
+            .create_listing(user, field_mint, 50_000_000, None)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&listing, &field_mint).await, 1);
+    farmer
+        .process_as_user(&[farmer.farm.cancel_listing(user, field_mint)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+    assert!(farmer.account_data(&listing).await.is_none());
+
+    // Listed again for 40, the shop takes its 2.5% fee of the sale
+    let paw_currency = farmer.token_balance(&paw, &currency_mint).await;
+    let treasury = farmer.token_balance(&farms_pda, &currency_mint).await;
+    farmer
+        .process_as_user(&[farmer
+            .farm
+            .create_listing(user, field_mint, 40_000_000, None)])
+        .await
+        .unwrap();
+    farmer
+        .process_as_paw(&[farmer.farm.buy_listing(paw, field_mint, user, 40_000_000)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&paw, &field_mint).await, 1);
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        USER_CURRENCY - 70_000_000 + 39_000_000
+    );
+    assert_eq!(
+        farmer.token_balance(&paw, &currency_mint).await,
+        paw_currency - 40_000_000
+    );
+    assert_eq!(
+        farmer.token_balance(&farms_pda, &currency_mint).await,
+        treasury + 1_000_000
+    );
+    assert!(farmer.account_data(&listing).await.is_none());
+}
+
+#[tokio::test]
+async fn leases_a_field_out_for_a_share_of_the_harvest() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::lease(LEASE_TERMS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let currency_mint = farmer.farm.spl_mint;
+    let paw_currency = farmer.token_balance(&paw, &currency_mint).await;
+
+    // The vault of the lease holds the field, the tenant plants, waters and harvests it through it
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 0);
+    farmer
+        .process_as_paw(&[farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                lessor: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .unverify_collection(&seed_mint, &farmer.collections.seed.clone())
+        .await;
+    for next_stage in [CropStage::Sapling, CropStage::Ripe] {
+        farmer
+            .process_as_paw(&[farmer.farm.water(
+                paw,
+                field_mint,
+                seed_mint,
+                "a1",
+                next_stage,
+                CropOptions {
+                    lessor: Some(user),
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+    }
+    farmer
+        .process_as_paw(&[farmer.farm.harvest_crop(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                lessor: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&paw, &seed_mint).await, 1);
+
+    // Besides the fee, the owner gets a fifth of the 10.5 the gold tomato is worth at the shop
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        USER_CURRENCY - 70_000_000 + 10_000_000 + 2_100_000
+    );
+    assert_eq!(
+        farmer.token_balance(&paw, &currency_mint).await,
+        paw_currency - 2_100_000
+    );
+
+    // Once the lease expires, the field goes back to its owner
+    farmer.advance_clock(LEASE_TERMS.duration).await;
+    farmer
+        .process_as_user(&[farmer.farm.end_lease(user, field_mint, Some(paw))])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+}
+
+#[tokio::test]
+async fn delegates_a_field_to_a_farmhand() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::delegate(Farmhand::ALL_ACTIONS).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+
+    // The vault of the farmhand holds the field, the farmhand plants, waters and harvests it for the
+    // owner without them signing, and the owner can still tend it through the vault
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 0);
+    farmer
+        .process_as_paw(&[farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .unverify_collection(&seed_mint, &farmer.collections.seed.clone())
+        .await;
+    farmer
+        .process_as_paw(&[farmer.farm.water(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Sapling,
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Ripe,
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .process_as_paw(&[farmer.farm.harvest_crop(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 1);
+    assert_eq!(farmer.token_balance(&paw, &seed_mint).await, 0);
+
+    // Revoking the farmhand gives the field back to its owner
+    farmer
+        .process_as_user(&[farmer.farm.revoke_farmhand(user, field_mint)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+    let farmhand = pda::find_farmhand_pda(&farmer.farm.farms_pda(), &field_mint).0;
+    assert!(farmer.account_data(&farmhand).await.is_none());
+}
+
+#[tokio::test]
+async fn harvests_into_produce_as_a_farmhand() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::plant().await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let produce_mint = pda::find_produce_mint(&farmer.farm.farms_pda(), 0).0;
+
+    farmer
+        .process_as_paw(&[farmer.farm.set_produce(0, PRODUCE_CONFIG)])
+        .await
+        .unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    farmer.water(&field_mint, &seed_mint).await.unwrap();
+    let expires_at = farmer.now().await + FARMHAND_DURATION;
+    farmer
+        .process_as_user(&[farmer.farm.delegate_farmhand(
+            user,
+            field_mint,
+            paw,
+            Farmhand::HARVEST,
+            expires_at,
+        )])
+        .await
+        .unwrap();
+
+    // The owner planted the crop, so the farmhand has no ATA for it yet to burn it from, and the
+    // produce goes to the owner
+    farmer
+        .process_as_paw(&[farmer.farm.harvest_crop(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                produce: true,
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&paw, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&user, &produce_mint).await, 3);
+    assert_eq!(farmer.token_balance(&paw, &produce_mint).await, 0);
+}
+
+#[tokio::test]
+async fn fertilizes_a_field_through_the_farmhand_vault() {
+    let mut farmer = Farmer::open_shop().await;
+    farmer
+        .process_as_paw(&[farmer.farm.update_growth_config(GrowthConfig {
+            stage_growth_time: 60 * 60,
+            ..GrowthConfig::default()
+        })])
+        .await
+        .unwrap();
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    let fertilizer_mint = farmer.stock_fertilizer(0).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    farmer.mint_tokens(&fertilizer_mint, &paw, 1).await;
+    let expires_at = farmer.now().await + FARMHAND_DURATION;
+    farmer
+        .process_as_user(&[farmer.farm.delegate_farmhand(
+            user,
+            field_mint,
+            paw,
+            Farmhand::WATER,
+            expires_at,
+        )])
+        .await
+        .unwrap();
+
+    // The field is in the farmhand vault now, so the farmhand fertilizes the growing crop through it
+    let result = farmer
+        .process_as_paw(&[farmer.farm.fertilize(
+            paw,
+            field_mint,
+            seed_mint,
+            fertilizer_mint,
+            "a1",
+            CropOptions::default(),
+        )])
+        .await;
+    assert_farmer_house_error(result, FarmerHouseError::OwnerMismatch);
+    farmer
+        .process_as_paw(&[farmer.farm.fertilize(
+            paw,
+            field_mint,
+            seed_mint,
+            fertilizer_mint,
+            "a1",
+            CropOptions {
+                farmhand: Some(user),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&paw, &fertilizer_mint).await, 0);
+}
+
+#[tokio::test]
+async fn farms_a_field_as_a_coop_splitting_the_sale() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::coop(true).await;
+    let user = farmer.user.pubkey();
+    let paw = farmer.paw.pubkey();
+    let currency_mint = farmer.farm.spl_mint;
+    let coop = pda::find_coop_pda(&farmer.farm.farms_pda(), &field_mint).0;
+    let coop_vault = pda::find_coop_vault_pda(&coop).0;
+
+    // The vault of the co-op holds the field, its members plant, water and harvest it through it
+    assert_eq!(farmer.token_balance(&coop_vault, &field_mint).await, 1);
+    farmer
+        .process_as_paw(&[farmer.farm.plant_seed(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .unverify_collection(&seed_mint, &farmer.collections.seed.clone())
+        .await;
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Sapling,
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .process_as_paw(&[farmer.farm.water(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Ripe,
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    farmer
+        .process_as_paw(&[farmer.farm.harvest_crop(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&coop_vault, &seed_mint).await, 1);
+
+    // Passing the co-op vault's own account as the shop's, the members would be paid for a crop the
+    // co-op keeps
+    let farms_pda = farmer.farm.farms_pda();
+    let instruction = with_account(
+        farmer
+            .farm
+            .sell_coop_crop(user, field_mint, seed_mint, &[user, paw], None, false),
+        &get_associated_token_address(&farms_pda, &seed_mint),
+        &get_associated_token_address(&coop_vault, &seed_mint),
+    );
+    let result = farmer.process_as_user(&[instruction]).await;
+    assert_farmer_house_error(result, FarmerHouseError::OwnerMismatch);
+
+    // Selling the harvest splits the 10.5 the gold tomato is worth three to one between the members
+    let user_currency = farmer.token_balance(&user, &currency_mint).await;
+    let paw_currency = farmer.token_balance(&paw, &currency_mint).await;
+    farmer
+        .process_as_user(&[farmer.farm.sell_coop_crop(
+            user,
+            field_mint,
+            seed_mint,
+            &[user, paw],
+            None,
+            false,
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&coop_vault, &seed_mint).await, 0);
+    assert_eq!(
+        farmer.token_balance(&user, &currency_mint).await,
+        user_currency + 7_875_000
+    );
+    assert_eq!(
+        farmer.token_balance(&paw, &currency_mint).await,
+        paw_currency + 2_625_000
+    );
+
+    // Holding three of the four shares, the user dissolves the co-op on their own and takes the field
+    farmer
+        .process_as_user(&[farmer.farm.dissolve_coop(user, field_mint, &[])])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+    assert!(farmer.account_data(&coop).await.is_none());
+}
+
+#[tokio::test]
+async fn clears_a_withered_crop_through_the_coop_vault() {
+    let (mut farmer, field_mint, seed_mint) = Farmer::coop(true).await;
+    let paw = farmer.paw.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let coop = pda::find_coop_pda(&farms_pda, &field_mint).0;
+    let coop_vault = pda::find_coop_vault_pda(&coop).0;
+    farmer
+        .process_as_paw(&[
+            farmer.farm.update_growth_config(GrowthConfig {
+                watering_window: 1,
+                wither_deadline: 2,
+                ..GrowthConfig::default()
+            }),
+            farmer.farm.plant_seed(
+                paw,
+                field_mint,
+                seed_mint,
+                "a1",
+                CropOptions {
+                    coop: true,
+                    ..Default::default()
+                },
+            ),
+        ])
+        .await
+        .unwrap();
+    farmer.advance_clock(10).await;
+
+    // The vault of the co-op moves the withered crop out of the field, the member clearing it burns it
+    farmer
+        .process_as_paw(&[farmer.farm.clear_slot(
+            paw,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                coop: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&paw, &seed_mint).await, 0);
+    assert_eq!(farmer.token_balance(&coop_vault, &field_mint).await, 1);
+    let crop_state = pda::find_crop_state_pda(&farms_pda, &seed_mint).0;
+    assert!(farmer.account_data(&crop_state).await.is_none());
+}
+
+#[tokio::test]
+async fn crafts_goods_from_a_recipe() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let input_mints = farmer.stock_recipe(60, 2).await;
+    let output_mint = pda::find_recipe_mint(&farms_pda, 0).0;
+    let recipe = pda::find_recipe_pda(&farms_pda, 0).0;
+    let craft_state = pda::find_craft_state_pda(&recipe, &user).0;
+
+    // Crafting burns the inputs right away, the output waits out the processing time
+    farmer
+        .process_as_user(&[farmer
+            .farm
+            .craft(user, 0, &mint_inputs(&input_mints), &[], true, 2)])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &input_mints[0]).await, 0);
+    assert_eq!(farmer.token_balance(&user, &input_mints[1]).await, 0);
+    assert_eq!(farmer.token_balance(&user, &output_mint).await, 0);
+
+    farmer.advance_clock(60).await;
+    farmer
+        .process_as_user(&[farmer.farm.collect_craft(user, 0, &[])])
+        .await
+        .unwrap();
+    assert_eq!(farmer.token_balance(&user, &output_mint).await, 4);
+    assert!(farmer.account_data(&craft_state).await.is_none());
+}
+
+#[tokio::test]
+async fn crafts_ketchup_from_ripe_tomatoes() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+
+    // Two ripe tomatoes make a bottle of ketchup, handed out from the bottles stocked in the shop
+    let ketchup_collection = farmer.mint_collection("Ketchup Collection", "KETCO").await;
+    let ketchup_mint = farmer
+        .mint_farm_nft("Ketchup", "KETCHUP", ketchup_collection)
+        .await;
+    farmer
+        .process_as_paw(&[farmer.farm.set_recipe(
+            0,
+            RecipeConfig {
+                inputs: vec![RecipeInput {
+                    mint: farmer.collections.ripe,
+                    amount: 2,
+                    collection: true,
+                }],
+                output_amount: 1,
+                processing_time: 0,
+                output_collection: Some(ketchup_collection),
+            },
+        )])
+        .await
+        .unwrap();
+
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    let second_mint = farmer.stock_seed().await;
+    farmer
+        .process_as_user(&[farmer.farm.buy_seed(user, second_mint, None)])
+        .await
+        .unwrap();
+    for (crop_mint, crop_slot) in [(seed_mint, "a1"), (second_mint, "b1")] {
+        farmer.plant_in(&field_mint, &crop_mint, crop_slot).await;
+        for next_stage in [CropStage::Sapling, CropStage::Ripe] {
+            farmer
+                .process_as_user(&[farmer.farm.water(
+                    user,
+                    field_mint,
+                    crop_mint,
+                    crop_slot,
+                    next_stage,
+                    CropOptions::default(),
+                )])
+                .await
+                .unwrap();
+        }
+        farmer
+            .process_as_user(&[farmer.farm.harvest_crop(
+                user,
+                field_mint,
+                crop_mint,
+                crop_slot,
+                CropOptions::default(),
+            )])
+            .await
+            .unwrap();
+    }
+
+    farmer
+        .process_as_user(&[farmer.farm.craft(
+            user,
+            0,
+            &[CraftInput::Nfts(vec![seed_mint, second_mint])],
+            &[ketchup_mint],
+            false,
+            1,
+        )])
+        .await
+        .unwrap();
+    // The tomatoes are burned along with their metadata
+    for crop_mint in [seed_mint, second_mint] {
+        assert_eq!(farmer.token_balance(&user, &crop_mint).await, 0);
+        assert!(farmer
+            .account_data(&pda::find_metadata(&crop_mint).0)
+            .await
+            .is_none());
+    }
+    assert_eq!(farmer.token_balance(&user, &ketchup_mint).await, 1);
+}
+
+#[tokio::test]
+async fn companion_planting_speeds_growth_and_adds_yield() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    let produce_mint = pda::find_produce_mint(&farms_pda, 0).0;
+
+    // Tomatoes grow twice as fast and yield two more produce next to other tomatoes
+    farmer
+        .process_as_paw(&[
+            farmer.farm.update_growth_config(GrowthConfig {
+                stage_growth_time: 60 * 60,
+                ..GrowthConfig::default()
+            }),
+            farmer.farm.set_produce(0, PRODUCE_CONFIG),
+            farmer.farm.set_companions(
+                0,
+                vec![CompanionEffect {
+                    neighbour_species_id: 0,
+                    growth_time_bps: -5000,
+                    yield_bonus: 2,
+                }],
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    let neighbour_mint = farmer.stock_seed().await;
+    farmer
+        .process_as_user(&[farmer.farm.buy_seed(user, neighbour_mint, None)])
+        .await
+        .unwrap();
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    farmer.plant_in(&field_mint, &neighbour_mint, "a2").await;
+
+    // Planting waits out the full growth time, watering next to the neighbour only half of it
+    farmer.advance_clock(60 * 60).await;
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Sapling,
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+    farmer.advance_clock(30 * 60).await;
+    farmer
+        .process_as_user(&[farmer.farm.water(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropStage::Ripe,
+            CropOptions::default(),
+        )])
+        .await
+        .unwrap();
+
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                produce: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(
+        farmer.token_balance(&user, &produce_mint).await,
+        PRODUCE_CONFIG.gold_yield + 2
+    );
+}
+
+/// Buys a seed of the species, plants it in `a1` of the field and grows it into produce. Returns the
+/// produce harvested.
+async fn grow_produce(farmer: &mut Farmer, field_mint: &Pubkey, species_id: Option<u8>) -> u64 {
+    let user = farmer.user.pubkey();
+    let produce_mint = pda::find_produce_mint(&farmer.farm.farms_pda(), species_id.unwrap_or(0)).0;
+    let balance_before = farmer.token_balance(&user, &produce_mint).await;
+
+    let seed_mint = farmer.stock_seed().await;
+    farmer
+        .process_as_user(&[
+            farmer.farm.buy_seed(user, seed_mint, species_id),
+            farmer.farm.plant_seed(
+                user,
+                *field_mint,
+                seed_mint,
+                "a1",
+                CropOptions {
+                    species_id,
+                    ..Default::default()
+                },
+            ),
+        ])
+        .await
+        .unwrap();
+    farmer
+        .unverify_collection(&seed_mint, &farmer.collections.seed.clone())
+        .await;
+    for next_stage in [CropStage::Sapling, CropStage::Ripe] {
+        farmer
+            .process_as_user(&[farmer.farm.water(
+                user,
+                *field_mint,
+                seed_mint,
+                "a1",
+                next_stage,
+                CropOptions {
+                    species_id,
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+    }
+    farmer
+        .process_as_user(&[farmer.farm.harvest_crop(
+            user,
+            *field_mint,
+            seed_mint,
+            "a1",
+            CropOptions {
+                species_id,
+                produce: true,
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+
+    farmer.token_balance(&user, &produce_mint).await - balance_before
+}
+
+#[tokio::test]
+async fn rotating_species_restores_the_soil() {
+    let mut farmer = Farmer::open_shop().await;
+    let user = farmer.user.pubkey();
+
+    // A second species grown from the same collections as the tomato
+    farmer
+        .process_as_paw(&[
+            farmer.farm.register_species(
+                1,
+                farmer.collections.seed,
+                farmer.collections.sapling,
+                farmer.collections.ripe,
+            ),
+            farmer.farm.set_produce(0, PRODUCE_CONFIG),
+            farmer.farm.set_produce(1, PRODUCE_CONFIG),
+        ])
+        .await
+        .unwrap();
+    let field_mint = farmer.stock_field().await;
+    farmer
+        .process_as_user(&[farmer.farm.buy_field(user, field_mint)])
+        .await
+        .unwrap();
+
+    // Each gold tomato depletes the soil, so the next one yields less
+    assert_eq!(grow_produce(&mut farmer, &field_mint, None).await, 3);
+    assert_eq!(grow_produce(&mut farmer, &field_mint, None).await, 2);
+
+    // Planting another species restores what the tomatoes took
+    assert_eq!(grow_produce(&mut farmer, &field_mint, Some(1)).await, 3);
+}
+
+#[tokio::test]
+async fn migrates_a_v1_farm_in_place() {
+    let mut farmer = Farmer::start_v1().await;
+    let user = farmer.user.pubkey();
+    let farms_pda = farmer.farm.farms_pda();
+    farmer
+        .process_as_paw(&[farmer.farm.migrate_farms_pda()])
+        .await
+        .unwrap();
+    assert_eq!(
+        farmer.account_data(&farms_pda).await.unwrap().len(),
+        FarmsPda::SPACE
+    );
+    assert_farmer_house_error(
+        farmer
+            .process_as_paw(&[farmer.farm.migrate_farms_pda()])
+            .await,
+        FarmerHouseError::FarmsPdaUpToDate,
+    );
+
+    // The farm keeps signing from its legacy address, through to planting a crop
+    let currency_mint = farmer.farm.spl_mint;
+    farmer
+        .transfer_tokens(&currency_mint, &farms_pda, SHOP_CURRENCY)
+        .await;
+    farmer
+        .process_as_paw(&[farmer.farm.initialize_constraint_model(SCHEMA_URI)])
+        .await
+        .unwrap();
+    let (field_mint, seed_mint) = farmer.buy_field_and_seed().await;
+    farmer.plant_in(&field_mint, &seed_mint, "a1").await;
+    assert_eq!(farmer.token_balance(&user, &field_mint).await, 1);
+    assert_eq!(farmer.token_balance(&user, &seed_mint).await, 0);
+}
@@ -7,8 +7,9 @@ import { createMint, getAccount, getAssociatedTokenAddress, mintTo, transfer, AS
 import { Metaplex, keypairIdentity, Sft, toBigNumber, SplTokenAmount, SftWithToken, NftWithToken, Nft, token } from "@metaplex-foundation/js";
 import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pubkey";
 import * as assert from "assert";
-import { findEscrowConstraintModelPda, findTriflePda, findEscrowPda } from "../helpers/pdas";
+import { findEscrowConstraintModelPda, findTriflePda, findEscrowPda, findCropStatePda, findSpeciesPda, findStageMetadataPda, findFieldStatePda, findProducePda, findCompanionsPda, findEventsPda } from "../helpers/pdas";
 import lumina from '@lumina-dev/test';
+import * as fs from "fs";
 
 lumina();
 
@@ -41,13 +42,20 @@ describe("farmer-house", () => {
   const metaplex = Metaplex.make(provider.connection)
     .use(keypairIdentity(PAW.payer));
 
-  const [farmsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+  const findFarmsPda = (instanceId: number) => anchor.web3.PublicKey.findProgramAddressSync(
     [
       anchor.utils.bytes.utf8.encode('farmer-house-farms'),
       program.programId.toBuffer(),
+      new anchor.BN(instanceId).toArrayLike(Buffer, "le", 2),
     ],
     program.programId
   );
+  const [farmsPda] = findFarmsPda(0);
+
+  const [farmerHouseProgramData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    anchor.web3.BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
 
   console.log("\n")
   console.log(`PAW address: ${PAW.publicKey.toBase58()}`)
@@ -177,6 +185,29 @@ describe("farmer-house", () => {
     console.log("All NFT collecitons minted")
   })
 
+  it("Rejects Farms Pda initialization by a foreign signer", async () => {
+    const farmsPdaTokenAccount: anchor.web3.PublicKey = await getAssociatedTokenAddress(currencyMint, farmsPda, true);
+
+    await assert.rejects(
+      program.methods.initializeFarmsPda(
+        0, tomatoSeedCollection.mint.address, tomatoSapplingCollection.mint.address, ripeTomatoCollection.mint.address, fieldCollection.mint.address
+      )
+        .accounts({
+          pdaAuthority: userWallet.publicKey,
+          farmsPda,
+          pdaAssociatedTokenAccount: farmsPdaTokenAccount,
+          splMint: currencyMint,
+          farmerHouseProgram: program.programId,
+          farmerHouseProgramData,
+        })
+        .signers([userWallet])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "AuthorityMismatch"
+    );
+
+    assert.equal(await provider.connection.getAccountInfo(farmsPda), null);
+  })
+
   it("Initializes Farms Pda", async () => {
 
     console.log("Initializing Farms PDA");
@@ -184,7 +215,7 @@ describe("farmer-house", () => {
     const farmsPdaTokenAccount: anchor.web3.PublicKey = await getAssociatedTokenAddress(currencyMint, farmsPda, true);
 
     const initializeSignature = await program.methods.initializeFarmsPda(
-      tomatoSeedCollection.mint.address, tomatoSapplingCollection.mint.address, ripeTomatoCollection.mint.address, fieldCollection.mint.address
+      0, tomatoSeedCollection.mint.address, tomatoSapplingCollection.mint.address, ripeTomatoCollection.mint.address, fieldCollection.mint.address
     )
       .accounts({
         pdaAuthority: PAW.publicKey,
@@ -192,6 +223,7 @@ describe("farmer-house", () => {
         pdaAssociatedTokenAccount: farmsPdaTokenAccount,
         splMint: currencyMint,
         farmerHouseProgram: program.programId,
+        farmerHouseProgramData,
       })
       .signers([PAW.payer])
       .rpc();
@@ -221,6 +253,111 @@ describe("farmer-house", () => {
     console.log("300 Tokens transferred to shop's token account");
   })
 
+  it("Initializes a second farm instance", async () => {
+    const [secondFarmsPda] = findFarmsPda(1);
+    const secondFarmsPdaTokenAccount = await getAssociatedTokenAddress(currencyMint, secondFarmsPda, true);
+
+    await program.methods.initializeFarmsPda(
+      1, tomatoSeedCollection.mint.address, tomatoSapplingCollection.mint.address, ripeTomatoCollection.mint.address, fieldCollection.mint.address
+    )
+      .accounts({
+        pdaAuthority: PAW.publicKey,
+        farmsPda: secondFarmsPda,
+        pdaAssociatedTokenAccount: secondFarmsPdaTokenAccount,
+        splMint: currencyMint,
+        farmerHouseProgram: program.programId,
+        farmerHouseProgramData,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const secondFarmsPdaInfo = await program.account.farmsPda.fetch(secondFarmsPda);
+    assert.equal(secondFarmsPdaInfo.instanceId, 1);
+    assert.equal(secondFarmsPdaInfo.version, 6);
+    assert.notEqual(secondFarmsPda.toBase58(), farmsPda.toBase58());
+
+    // Each instance keeps its own shop balance
+    const secondFarmsPdaTokenAccountInfo = await getAccount(provider.connection, secondFarmsPdaTokenAccount);
+    assert.equal(secondFarmsPdaTokenAccountInfo.amount, 0);
+  })
+
+  it("Migrates a v1 Farms Pda", async () => {
+    // Preloaded by Anchor.toml from tests/fixtures, in the layout of the first release, at the address it
+    // was derived at before farms had instance ids
+    const [v1FarmsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [anchor.utils.bytes.utf8.encode('farmer-house-farms'), program.programId.toBuffer()],
+      program.programId
+    );
+    const v1Authority = anchor.web3.Keypair.fromSecretKey(
+      Uint8Array.from(JSON.parse(fs.readFileSync("tests/fixtures/farms-pda-v1-authority.json", "utf-8")))
+    );
+
+    const airdropSignature = await provider.connection.requestAirdrop(v1Authority.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    const latestBlockHash = await provider.connection.getLatestBlockhash();
+    await provider.connection.confirmTransaction({
+      blockhash: latestBlockHash.blockhash,
+      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+      signature: airdropSignature,
+    });
+
+    await assert.rejects(program.account.farmsPda.fetch(v1FarmsPda));
+
+    await assert.rejects(
+      program.methods.migrateFarmsPda()
+        .accounts({
+          farmsPda: v1FarmsPda,
+          pdaAuthority: userWallet.publicKey,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([userWallet])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "AuthorityMismatch"
+    );
+
+    await program.methods.migrateFarmsPda()
+      .accounts({
+        farmsPda: v1FarmsPda,
+        pdaAuthority: v1Authority.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([v1Authority])
+      .rpc();
+
+    const v1FarmsPdaInfo = await program.account.farmsPda.fetch(v1FarmsPda);
+    assert.equal(v1FarmsPdaInfo.version, 6);
+    assert.equal(v1FarmsPdaInfo.instanceId, 0);
+    assert.ok(v1FarmsPdaInfo.legacySeeds);
+    assert.equal(v1FarmsPdaInfo.authorityAddress.toBase58(), v1Authority.publicKey.toBase58());
+    assert.equal(v1FarmsPdaInfo.growthConfig.wateringWindow.toNumber(), 86400);
+    assert.equal(v1FarmsPdaInfo.prices.field.toNumber(), 70000000);
+    assert.equal(v1FarmsPdaInfo.prices.ripeBuyback.toNumber(), 7000000);
+    assert.equal(v1FarmsPdaInfo.prices.fieldBuyback.toNumber(), 35000000);
+    assert.equal(v1FarmsPdaInfo.prices.marketFeeBps, 250);
+
+    // Instructions keep deriving the migrated farm without an instance id
+    await program.methods.updateGrowthConfig({ wateringWindow: new anchor.BN(3600), witherDeadline: new anchor.BN(7200), stageGrowthTime: new anchor.BN(0), fertilizerBoost: new anchor.BN(0) })
+      .accounts({
+        farmsPda: v1FarmsPda,
+        pdaAuthority: v1Authority.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([v1Authority])
+      .rpc();
+    assert.equal((await program.account.farmsPda.fetch(v1FarmsPda)).growthConfig.wateringWindow.toNumber(), 3600);
+
+    await assert.rejects(
+      program.methods.migrateFarmsPda()
+        .accounts({
+          farmsPda: v1FarmsPda,
+          pdaAuthority: v1Authority.publicKey,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([v1Authority])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "FarmsPdaUpToDate"
+    );
+  })
+
   it("Mints necessary NFTs", async () => {
 
     console.log("Minting field")
@@ -272,8 +409,6 @@ describe("farmer-house", () => {
         escrowConstraintModel: escrowConstraintModelAddress,
         farmsPda,
         pdaAuthority: PAW.publicKey,
-        tomatoSeedCollectionMint: tomatoSeedCollection.mint.address,
-        tomatoSeedCollectionMetadata: tomatoSeedCollection.metadataAddress,
         trifleProgram: trifle.PROGRAM_ID,
         farmerHouseProgram: program.programId,
         instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -365,6 +500,7 @@ describe("farmer-house", () => {
         seedAtaDestination,
         seedMint: tomatoNft.mint.address,
         seedMetadata: tomatoNft.metadataAddress,
+        species: null,
         farmerHouseProgram: program.programId
       })
       .signers([userWallet])
@@ -388,6 +524,8 @@ describe("farmer-house", () => {
     const fieldTokenAccount = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey, true);
     const attributeSrcTokenAccount = await getAssociatedTokenAddress(tomatoNft.mint.address, userWallet.publicKey, true)
     const attributeDstTokenAccount = await getAssociatedTokenAddress(tomatoNft.mint.address, escrowAccountAddress, true);
+    const [cropState] = findCropStatePda(farmsPda, tomatoNft.mint.address, program.programId);
+    const [fieldState] = findFieldStatePda(farmsPda, fieldNft.mint.address, program.programId);
 
     const plantSeedSignature = await program.methods.plantSeed("a1")
       .accounts({
@@ -403,6 +541,16 @@ describe("farmer-house", () => {
         attributeSrcTokenAccount,
         attributeDstTokenAccount,
         attributeMetadata: tomatoNft.metadataAddress,
+        cropState,
+        species: null,
+        events: findEventsPda(farmsPda, program.programId)[0],
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        fieldState,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenMetadataProgram: metadata.PROGRAM_ID,
@@ -419,6 +567,10 @@ describe("farmer-house", () => {
       signature: plantSeedSignature,
     });
 
+    const fieldStateInfo = await program.account.fieldState.fetch(fieldState);
+    assert.equal(fieldStateInfo.slots[0].cropMint.toBase58(), tomatoNft.mint.address.toBase58());
+    assert.deepEqual(fieldStateInfo.slots[0].stage, { seed: {} });
+
     metaplex.nfts().unverifyCollection({
       mintAddress: tomatoNft.mint.address,
       collectionMintAddress: tomatoSeedCollection.mint.address,
@@ -430,6 +582,43 @@ describe("farmer-house", () => {
 
     const fieldAssociatedToken = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey);
     const [trifleAddress] = findTriflePda(fieldNft.mint.address, farmsPda);
+    const [cropState] = findCropStatePda(farmsPda, tomatoNft.mint.address, program.programId);
+    const [fieldState] = findFieldStatePda(farmsPda, fieldNft.mint.address, program.programId);
+    const [saplingMetadata] = findStageMetadataPda(farmsPda, 0, 1, program.programId);
+    const [ripeMetadata] = findStageMetadataPda(farmsPda, 0, 2, program.programId);
+
+    // Creators are left out, so the crop keeps the ones it was minted with
+    await program.methods.setStageMetadata(0, { sapling: {} }, {
+      name: "Tomato Sapling",
+      symbol: "TOMATO",
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      sellerFeeBasisPoints: 500,
+      creators: null,
+    })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        stageMetadata: saplingMetadata,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    await program.methods.setStageMetadata(0, { ripe: {} }, {
+      name: "Ripe Tomato",
+      symbol: "TOMATO",
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      sellerFeeBasisPoints: 500,
+      creators: null,
+    })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        stageMetadata: ripeMetadata,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
 
     console.log("Watering crop for the first time")
     const firstWaterSignature = await program.methods.water("a1")
@@ -438,7 +627,18 @@ describe("farmer-house", () => {
         userAccount: userWallet.publicKey,
         fieldMint: fieldNft.mint.address,
         fieldAssociatedToken,
+        cropMint: tomatoNft.mint.address,
         cropMetadata: tomatoNft.metadataAddress,
+        cropState,
+        species: null,
+        stageMetadata: saplingMetadata,
+        companions: findCompanionsPda(farmsPda, 0, program.programId)[0],
+        mutation: null,
+        slotHashes: null,
+        lease: null,
+        farmhand: null,
+        coop: null,
+        fieldState,
         trifleAccount: trifleAddress,
         tokenMetadataProgram: metadata.PROGRAM_ID,
         farmerHouseProgram: program.programId,
@@ -454,6 +654,13 @@ describe("farmer-house", () => {
     });
     console.log("Crop watered one time");
 
+    await metaplex.nfts().findByMint({ mintAddress: tomatoNft.mint.address }).then(data => {
+      assert.equal(data.name, "Tomato Sapling");
+      assert.equal(data.sellerFeeBasisPoints, 500);
+      assert.equal(data.creators[0].address.toBase58(), PAW.publicKey.toBase58());
+      assert.ok(data.creators[0].verified);
+    });
+
     console.log("Watering crop for the second time")
     const secondWaterSignature = await program.methods.water("a1")
       .accounts({
@@ -461,7 +668,18 @@ describe("farmer-house", () => {
         userAccount: userWallet.publicKey,
         fieldMint: fieldNft.mint.address,
         fieldAssociatedToken,
+        cropMint: tomatoNft.mint.address,
         cropMetadata: tomatoNft.metadataAddress,
+        cropState,
+        species: null,
+        stageMetadata: ripeMetadata,
+        companions: findCompanionsPda(farmsPda, 0, program.programId)[0],
+        mutation: null,
+        slotHashes: null,
+        lease: null,
+        farmhand: null,
+        coop: null,
+        fieldState,
         trifleAccount: trifleAddress,
         tokenMetadataProgram: metadata.PROGRAM_ID,
         farmerHouseProgram: program.programId,
@@ -477,9 +695,13 @@ describe("farmer-house", () => {
     });
     console.log("Crop watered twice");
 
-    await metaplex.nfts().findByMint({ mintAddress: tomatoNft.mint.address }).then(data =>
-      assert.equal(data.collection.address.toBase58(), ripeTomatoCollection.mint.address.toBase58())
-    );
+    await metaplex.nfts().findByMint({ mintAddress: tomatoNft.mint.address }).then(data => {
+      assert.equal(data.collection.address.toBase58(), ripeTomatoCollection.mint.address.toBase58());
+      assert.equal(data.name, "Ripe Tomato");
+    });
+
+    const fieldStateInfo = await program.account.fieldState.fetch(fieldState);
+    assert.deepEqual(fieldStateInfo.slots[0].stage, { ripe: {} });
   })
 
   it("Harvests crop", async () => {
@@ -489,6 +711,8 @@ describe("farmer-house", () => {
     const fieldTokenAccount = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey, true);
     const attributeSrcTokenAccount = await getAssociatedTokenAddress(tomatoNft.mint.address, escrowAccountAddress, true)
     const attributeDstTokenAccount = await getAssociatedTokenAddress(tomatoNft.mint.address, userWallet.publicKey);
+    const [cropState] = findCropStatePda(farmsPda, tomatoNft.mint.address, program.programId);
+    const [fieldState] = findFieldStatePda(farmsPda, fieldNft.mint.address, program.programId);
 
     const plantSeedSignature = await program.methods.harvestCrop("a1")
       .accounts({
@@ -505,6 +729,26 @@ describe("farmer-house", () => {
         attributeSrcTokenAccount,
         attributeDstTokenAccount,
         attributeMetadata: tomatoNft.metadataAddress,
+        attributeEdition: null,
+        cropState,
+        species: null,
+        fieldState,
+        produce: findProducePda(farmsPda, 0, program.programId)[0],
+        produceMint: null,
+        produceTokenAccount: null,
+        companions: findCompanionsPda(farmsPda, 0, program.programId)[0],
+        events: findEventsPda(farmsPda, program.programId)[0],
+        slotHashes: null,
+        mutation: null,
+        lease: null,
+        leaseVault: null,
+        splAtaSource: null,
+        lessorSplAta: null,
+        farmhand: null,
+        farmhandVault: null,
+        farmhandOwner: null,
+        coop: null,
+        coopVault: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenMetadataProgram: metadata.PROGRAM_ID,
@@ -522,9 +766,17 @@ describe("farmer-house", () => {
       signature: plantSeedSignature,
     });
 
+    // Both waterings happened inside the watering window
+    const cropStateInfo = await program.account.cropState.fetch(cropState);
+    assert.equal(cropStateInfo.onTimeWaterings, 2);
+    assert.equal(cropStateInfo.missedWindows, 0);
+    assert.deepEqual(cropStateInfo.grade, { gold: {} });
+
+    const fieldStateInfo = await program.account.fieldState.fetch(fieldState);
+    assert.equal(fieldStateInfo.slots[0].stage, null);
   })
 
-  it("Buys Seed", async () => {
+  it("Sells graded crop", async () => {
     // Get ATA address for currencyMint + userWallet (initialized)
     const splAtaSource = await getAssociatedTokenAddress(currencyMint, farmsPda, true);
     // Get ATA address for currencyMint + farmsPda (initialized)
@@ -534,10 +786,13 @@ describe("farmer-house", () => {
     const cropAtaSource = await getAssociatedTokenAddress(tomatoNft.mint.address, userWallet.publicKey);
     // Get ATA address for seedNft mint + userFarmPda (*un*initialized)
     const cropAtaDestination = await getAssociatedTokenAddress(tomatoNft.mint.address, farmsPda, true);
+    const [cropState] = findCropStatePda(farmsPda, tomatoNft.mint.address, program.programId);
+
+    const balanceBefore = (await getAccount(provider.connection, splAtaDestination)).amount;
 
     console.log("Selling crop");
 
-    const sellCropSignature = await program.methods.sellCrop()
+    const sellCropSignature = await program.methods.sellCrop(new anchor.BN(1))
       .accounts({
         farmsPda,
         userAccount: userWallet.publicKey,
@@ -547,6 +802,12 @@ describe("farmer-house", () => {
         cropAtaSource,
         cropAtaDestination,
         cropMetadata: tomatoNft.metadataAddress,
+        cropState,
+        species: null,
+        produce: null,
+        mutation: null,
+        coop: null,
+        coopVault: null,
         farmerHouseProgram: program.programId
       })
       .signers([userWallet])
@@ -559,7 +820,548 @@ describe("farmer-house", () => {
       signature: sellCropSignature,
     });
 
+    // A gold ripe tomato sells for 1.5x the base 7000000
+    const balanceAfter = (await getAccount(provider.connection, splAtaDestination)).amount;
+    assert.equal(Number(balanceAfter - balanceBefore), 10500000);
+
     console.log("Crop Sold");
   })
 
+  it("Withers a neglected crop and clears its slot", async () => {
+    const [trifleAddress] = findTriflePda(fieldNft.mint.address, farmsPda);
+    const [escrowAccountAddress] = findEscrowPda(fieldNft.mint.address, 1, trifleAddress);
+    const fieldTokenAccount = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey);
+
+    // Shorten the deadlines so the crop withers within the test
+    await program.methods.updateGrowthConfig({ wateringWindow: new anchor.BN(1), witherDeadline: new anchor.BN(2), stageGrowthTime: new anchor.BN(0), fertilizerBoost: new anchor.BN(43200) })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const { nft: neglectedNft } = await metaplex.nfts().create({
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      name: "Tomato Seed",
+      symbol: "TOMATO",
+      sellerFeeBasisPoints: 0,
+      tokenOwner: userWallet.publicKey,
+      isMutable: true,
+      collection: tomatoSeedCollection.address,
+      collectionAuthority: PAW.payer
+    })
+
+    const userCropTokenAccount = await getAssociatedTokenAddress(neglectedNft.mint.address, userWallet.publicKey);
+    const escrowCropTokenAccount = await getAssociatedTokenAddress(neglectedNft.mint.address, escrowAccountAddress, true);
+    const [cropState] = findCropStatePda(farmsPda, neglectedNft.mint.address, program.programId);
+    const [fieldState] = findFieldStatePda(farmsPda, fieldNft.mint.address, program.programId);
+
+    await program.methods.plantSeed("a2")
+      .accounts({
+        trifleAccount: trifleAddress,
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        escrowConstraintModel: escrowConstraintModelAddress,
+        escrowAccount: escrowAccountAddress,
+        fieldMint: fieldNft.mint.address,
+        fieldTokenAccount,
+        fieldMasterEdition: fieldNft.edition.address,
+        attributeMint: neglectedNft.mint.address,
+        attributeSrcTokenAccount: userCropTokenAccount,
+        attributeDstTokenAccount: escrowCropTokenAccount,
+        attributeMetadata: neglectedNft.metadataAddress,
+        cropState,
+        species: null,
+        events: findEventsPda(farmsPda, program.programId)[0],
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        fieldState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: metadata.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        trifleProgram: trifle.PROGRAM_ID,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    await delay(4000);
+
+    await assert.rejects(
+      program.methods.water("a2")
+        .accounts({
+          farmsPda,
+          userAccount: userWallet.publicKey,
+          fieldMint: fieldNft.mint.address,
+          fieldAssociatedToken: fieldTokenAccount,
+          cropMint: neglectedNft.mint.address,
+          cropMetadata: neglectedNft.metadataAddress,
+          cropState,
+          species: null,
+          stageMetadata: findStageMetadataPda(farmsPda, 0, 1, program.programId)[0],
+          companions: findCompanionsPda(farmsPda, 0, program.programId)[0],
+          mutation: null,
+          slotHashes: null,
+          lease: null,
+          farmhand: null,
+          coop: null,
+          fieldState,
+          trifleAccount: trifleAddress,
+          tokenMetadataProgram: metadata.PROGRAM_ID,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([userWallet])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "CropWithered"
+    );
+
+    await program.methods.clearSlot("a2")
+      .accounts({
+        trifleAccount: trifleAddress,
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        escrowConstraintModel: escrowConstraintModelAddress,
+        escrowAccount: escrowAccountAddress,
+        fieldMint: fieldNft.mint.address,
+        fieldTokenAccount,
+        fieldEdition: fieldNft.edition.address,
+        fieldMetadata: fieldNft.metadataAddress,
+        attributeMint: neglectedNft.mint.address,
+        attributeSrcTokenAccount: escrowCropTokenAccount,
+        attributeDstTokenAccount: userCropTokenAccount,
+        attributeMetadata: neglectedNft.metadataAddress,
+        cropState,
+        species: null,
+        fieldState,
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: metadata.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        trifleProgram: trifle.PROGRAM_ID,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    const userCropTokenAccountInfo = await getAccount(provider.connection, userCropTokenAccount);
+    assert.equal(userCropTokenAccountInfo.amount, 0);
+    assert.equal(await provider.connection.getAccountInfo(cropState), null);
+
+    // Restore the default deadlines
+    await program.methods.updateGrowthConfig({ wateringWindow: new anchor.BN(86400), witherDeadline: new anchor.BN(259200), stageGrowthTime: new anchor.BN(0), fertilizerBoost: new anchor.BN(43200) })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+  })
+
+  it("Fertilizes a growing crop", async () => {
+    const [trifleAddress] = findTriflePda(fieldNft.mint.address, farmsPda);
+    const [escrowAccountAddress] = findEscrowPda(fieldNft.mint.address, 1, trifleAddress);
+    const fieldTokenAccount = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey);
+
+    const fertilizerMint = await createMint(provider.connection, PAW.payer, PAW.publicKey, PAW.publicKey, 0);
+    const pdaFertilizerTokenAccount = await getAssociatedTokenAddress(fertilizerMint, farmsPda, true);
+    const userFertilizerTokenAccount = await getAssociatedTokenAddress(fertilizerMint, userWallet.publicKey);
+
+    await program.methods.initializeFertilizer()
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        pdaFertilizerTokenAccount,
+        fertilizerMint,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    await mintTo(provider.connection, PAW.payer, fertilizerMint, pdaFertilizerTokenAccount, PAW.publicKey, 10);
+
+    await program.methods.buyFertilizer(new anchor.BN(1))
+      .accounts({
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        splAtaSource: await getAssociatedTokenAddress(currencyMint, userWallet.publicKey),
+        splAtaDestination: await getAssociatedTokenAddress(currencyMint, farmsPda, true),
+        splMint: currencyMint,
+        fertilizerAtaSource: pdaFertilizerTokenAccount,
+        fertilizerAtaDestination: userFertilizerTokenAccount,
+        fertilizerMint,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    // Each stage now takes an hour to grow, which one unit of fertilizer skips
+    await program.methods.updateGrowthConfig({ wateringWindow: new anchor.BN(86400), witherDeadline: new anchor.BN(259200), stageGrowthTime: new anchor.BN(3600), fertilizerBoost: new anchor.BN(3600) })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const { nft: fertilizedNft } = await metaplex.nfts().create({
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      name: "Tomato Seed",
+      symbol: "TOMATO",
+      sellerFeeBasisPoints: 0,
+      tokenOwner: userWallet.publicKey,
+      isMutable: true,
+      collection: tomatoSeedCollection.address,
+      collectionAuthority: PAW.payer
+    })
+
+    await metaplex.nfts().update({
+      nftOrSft: fertilizedNft,
+      newUpdateAuthority: farmsPda,
+    })
+
+    const [cropState] = findCropStatePda(farmsPda, fertilizedNft.mint.address, program.programId);
+
+    const [fieldState] = findFieldStatePda(farmsPda, fieldNft.mint.address, program.programId);
+
+    await program.methods.plantSeed("a3")
+      .accounts({
+        trifleAccount: trifleAddress,
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        escrowConstraintModel: escrowConstraintModelAddress,
+        escrowAccount: escrowAccountAddress,
+        fieldMint: fieldNft.mint.address,
+        fieldTokenAccount,
+        fieldMasterEdition: fieldNft.edition.address,
+        attributeMint: fertilizedNft.mint.address,
+        attributeSrcTokenAccount: await getAssociatedTokenAddress(fertilizedNft.mint.address, userWallet.publicKey),
+        attributeDstTokenAccount: await getAssociatedTokenAddress(fertilizedNft.mint.address, escrowAccountAddress, true),
+        attributeMetadata: fertilizedNft.metadataAddress,
+        cropState,
+        species: null,
+        events: findEventsPda(farmsPda, program.programId)[0],
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        fieldState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: metadata.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        trifleProgram: trifle.PROGRAM_ID,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    const waterAccounts = {
+      farmsPda,
+      userAccount: userWallet.publicKey,
+      fieldMint: fieldNft.mint.address,
+      fieldAssociatedToken: fieldTokenAccount,
+      cropMint: fertilizedNft.mint.address,
+      cropMetadata: fertilizedNft.metadataAddress,
+      cropState,
+      species: null,
+      stageMetadata: findStageMetadataPda(farmsPda, 0, 1, program.programId)[0],
+      companions: findCompanionsPda(farmsPda, 0, program.programId)[0],
+      mutation: null,
+      slotHashes: null,
+      lease: null,
+      farmhand: null,
+      coop: null,
+      fieldState,
+      trifleAccount: trifleAddress,
+      tokenMetadataProgram: metadata.PROGRAM_ID,
+      farmerHouseProgram: program.programId,
+    };
+
+    await assert.rejects(
+      program.methods.water("a3").accounts(waterAccounts).signers([userWallet]).rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "CropGrowing"
+    );
+
+    await program.methods.fertilize("a3")
+      .accounts({
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        fieldMint: fieldNft.mint.address,
+        fieldAssociatedToken: fieldTokenAccount,
+        trifleAccount: trifleAddress,
+        cropMint: fertilizedNft.mint.address,
+        cropState,
+        fieldState,
+        fertilizerMint,
+        fertilizerTokenAccount: userFertilizerTokenAccount,
+        lease: null,
+        farmhand: null,
+        coop: null,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    const userFertilizerTokenAccountInfo = await getAccount(provider.connection, userFertilizerTokenAccount);
+    assert.equal(userFertilizerTokenAccountInfo.amount, 0);
+
+    await program.methods.water("a3").accounts(waterAccounts).signers([userWallet]).rpc();
+
+    await metaplex.nfts().findByMint({ mintAddress: fertilizedNft.mint.address }).then(data =>
+      assert.equal(data.collection.address.toBase58(), tomatoSapplingCollection.mint.address.toBase58())
+    );
+
+    // Restore the default growth config
+    await program.methods.updateGrowthConfig({ wateringWindow: new anchor.BN(86400), witherDeadline: new anchor.BN(259200), stageGrowthTime: new anchor.BN(0), fertilizerBoost: new anchor.BN(43200) })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+  })
+
+  it("Equips and unequips a tool", async () => {
+    const [trifleAddress] = findTriflePda(fieldNft.mint.address, farmsPda);
+    const [escrowAccountAddress] = findEscrowPda(fieldNft.mint.address, 1, trifleAddress);
+    const fieldTokenAccount = await getAssociatedTokenAddress(fieldNft.mint.address, userWallet.publicKey);
+
+    const { nft: toolCollection } = await metaplex.nfts().create({
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      name: "Tool Collection",
+      symbol: "TOOLCO",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+
+    await program.methods.addToolSlot()
+      .accounts({
+        escrowConstraintModel: escrowConstraintModelAddress,
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        toolCollectionMetadata: toolCollection.metadataAddress,
+        toolCollectionMint: toolCollection.mint.address,
+        trifleProgram: trifle.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const { nft: wateringCan } = await metaplex.nfts().create({
+      uri: "https://fossil-test.fra1.digitaloceanspaces.com/caveman-test-metadata.json",
+      name: "Watering Can",
+      symbol: "TOOL",
+      sellerFeeBasisPoints: 0,
+      tokenOwner: userWallet.publicKey,
+      collection: toolCollection.address,
+      collectionAuthority: PAW.payer
+    })
+
+    const userToolTokenAccount = await getAssociatedTokenAddress(wateringCan.mint.address, userWallet.publicKey);
+    const escrowToolTokenAccount = await getAssociatedTokenAddress(wateringCan.mint.address, escrowAccountAddress, true);
+
+    await program.methods.equipTool()
+      .accounts({
+        trifleAccount: trifleAddress,
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        escrowConstraintModel: escrowConstraintModelAddress,
+        escrowAccount: escrowAccountAddress,
+        fieldMint: fieldNft.mint.address,
+        fieldTokenAccount,
+        fieldMasterEdition: fieldNft.edition.address,
+        attributeMint: wateringCan.mint.address,
+        attributeSrcTokenAccount: userToolTokenAccount,
+        attributeDstTokenAccount: escrowToolTokenAccount,
+        attributeMetadata: wateringCan.metadataAddress,
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: metadata.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        trifleProgram: trifle.PROGRAM_ID,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    const trifleInfo = await trifle.Trifle.fromAccountAddress(provider.connection, trifleAddress);
+    assert.equal(trifleInfo.tokens.get("tool")[0].mint.toBase58(), wateringCan.mint.address.toBase58());
+
+    await program.methods.unequipTool()
+      .accounts({
+        trifleAccount: trifleAddress,
+        farmsPda,
+        userAccount: userWallet.publicKey,
+        escrowConstraintModel: escrowConstraintModelAddress,
+        escrowAccount: escrowAccountAddress,
+        fieldMint: fieldNft.mint.address,
+        fieldTokenAccount,
+        fieldEdition: fieldNft.edition.address,
+        fieldMetadata: fieldNft.metadataAddress,
+        attributeMint: wateringCan.mint.address,
+        attributeSrcTokenAccount: escrowToolTokenAccount,
+        attributeDstTokenAccount: userToolTokenAccount,
+        attributeMetadata: wateringCan.metadataAddress,
+        lease: null,
+        leaseVault: null,
+        farmhand: null,
+        farmhandVault: null,
+        coop: null,
+        coopVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: metadata.PROGRAM_ID,
+        farmerHouseProgram: program.programId,
+        trifleProgram: trifle.PROGRAM_ID,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([userWallet])
+      .rpc();
+
+    const userToolTokenAccountInfo = await getAccount(provider.connection, userToolTokenAccount);
+    assert.equal(userToolTokenAccountInfo.amount, 1);
+  })
+
+  it("Registers a species", async () => {
+    const [species] = findSpeciesPda(farmsPda, 1, program.programId);
+    const seedCollection = anchor.web3.Keypair.generate().publicKey;
+    const saplingCollection = anchor.web3.Keypair.generate().publicKey;
+    const ripeCollection = anchor.web3.Keypair.generate().publicKey;
+
+    await program.methods.registerSpecies(1, seedCollection, saplingCollection, ripeCollection)
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        species,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const speciesInfo = await program.account.species.fetch(species);
+    assert.equal(speciesInfo.speciesId, 1);
+    assert.equal(speciesInfo.seedCollectionAddress.toBase58(), seedCollection.toBase58());
+    assert.equal(speciesInfo.saplingCollectionAddress.toBase58(), saplingCollection.toBase58());
+    assert.equal(speciesInfo.ripeCollectionAddress.toBase58(), ripeCollection.toBase58());
+
+    // The tomato is built into the farms PDA and can't be registered again
+    const [tomatoSpecies] = findSpeciesPda(farmsPda, 0, program.programId);
+    await assert.rejects(
+      program.methods.registerSpecies(0, seedCollection, saplingCollection, ripeCollection)
+        .accounts({
+          farmsPda,
+          pdaAuthority: PAW.publicKey,
+          species: tomatoSpecies,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([PAW.payer])
+        .rpc()
+    );
+  })
+
+  it("Updates prices and withdraws from the treasury", async () => {
+    const prices = {
+      field: new anchor.BN(70000000),
+      seed: new anchor.BN(2000000),
+      fertilizer: new anchor.BN(1000000),
+      seedBuyback: new anchor.BN(2000000),
+      saplingBuyback: new anchor.BN(3500000),
+      ripeBuyback: new anchor.BN(7000000),
+      fieldBuyback: new anchor.BN(35000000),
+      marketFeeBps: 250,
+    };
+
+    await assert.rejects(
+      program.methods.updatePrices({ ...prices, field: new anchor.BN(0) })
+        .accounts({
+          farmsPda,
+          pdaAuthority: PAW.publicKey,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([PAW.payer])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "InvalidPrices"
+    );
+
+    await assert.rejects(
+      program.methods.updatePrices(prices)
+        .accounts({
+          farmsPda,
+          pdaAuthority: userWallet.publicKey,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([userWallet])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "AuthorityMismatch"
+    );
+
+    await program.methods.updatePrices({ ...prices, seed: new anchor.BN(2500000) })
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    const farmsPdaInfo = await program.account.farmsPda.fetch(farmsPda);
+    assert.equal(farmsPdaInfo.prices.seed.toNumber(), 2500000);
+    assert.equal(farmsPdaInfo.prices.field.toNumber(), 70000000);
+
+    const splAtaSource = await getAssociatedTokenAddress(currencyMint, farmsPda, true);
+    const splAtaDestination = await getAssociatedTokenAddress(currencyMint, PAW.publicKey);
+    const treasuryBefore = (await getAccount(provider.connection, splAtaSource)).amount;
+    const authorityBefore = (await getAccount(provider.connection, splAtaDestination)).amount;
+
+    await assert.rejects(
+      program.methods.withdrawTreasury(new anchor.BN((treasuryBefore + BigInt(1)).toString()))
+        .accounts({
+          farmsPda,
+          pdaAuthority: PAW.publicKey,
+          splAtaSource,
+          splAtaDestination,
+          farmerHouseProgram: program.programId,
+        })
+        .signers([PAW.payer])
+        .rpc(),
+      (err: anchor.AnchorError) => err.error.errorCode.code === "AmountMismatch"
+    );
+
+    await program.methods.withdrawTreasury(new anchor.BN(1000000))
+      .accounts({
+        farmsPda,
+        pdaAuthority: PAW.publicKey,
+        splAtaSource,
+        splAtaDestination,
+        farmerHouseProgram: program.programId,
+      })
+      .signers([PAW.payer])
+      .rpc();
+
+    assert.equal((await getAccount(provider.connection, splAtaSource)).amount, treasuryBefore - BigInt(1000000));
+    assert.equal((await getAccount(provider.connection, splAtaDestination)).amount, authorityBefore + BigInt(1000000));
+  })
+
 });
\ No newline at end of file